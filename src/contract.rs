@@ -1,26 +1,64 @@
 use crate::{
     error::ContractError,
     msg::{
-        CollectionExecuteMsg, CollectionQueryMsg, ExecuteMsg, GetPaymentParamsResponse,
-        InstantiateMsg, MigrateMsg, NameServiceExecuteMsgResponse, PaymentParams, QueryMsg,
-        SudoMsg,
+        AllowlistEligibilityResponse, AllowlistProof, BatchItemFailure, BatchMode, BatchResponse,
+        Charset, ClaimableBalanceResponse, CollectionExecuteMsg, CollectionInstantiateMsg,
+        CollectionMsgKind, CollectionPaymentParams, CollectionQueryMsg, CollectionRecord,
+        CurrentMintPriceResponse, CurrentPhaseResponse, Curve, DataFormat, DutchAuction,
+        ExecuteMsg, GetCollectionPaymentParamsResponse, GetMintLimitsResponse,
+        GetNameRulesResponse, GetPassThroughPoliciesResponse, GetPaymentParamsResponse,
+        HasRoleResponse, InstantiateMsg, KindPassThroughPolicy,
+        ListCollectionPaymentParamsResponse, ListCollectionsResponse, ListRoleMembersResponse,
+        ManagedCollection, MigrateMsg, MintLimits, MintPhase, NameExpiryResponse, NameRules,
+        NameServiceExecuteMsgResponse, PassThroughData, PassThroughFailure, PassThroughItem,
+        PassThroughOptions, PassThroughPolicy, PauseInfo, PauseStatusResponse, PaymentParams,
+        PayoutMode, PredictCollectionAddressResponse, PricingRule, QueryMsg, QuoteMintResponse,
+        QuoteNameResponse, ReceiveMsg, Role, SudoMsg, MAX_BATCH_ITEMS, SECONDS_PER_YEAR,
+        TOTAL_SHARE_BPS,
+    },
+    state::{
+        AuctionPurchase, AuctionSales, PendingBatch, PendingBatchItem, PendingMint,
+        PendingPassThrough, AUCTION_PURCHASES, AUCTION_SALES, BATCH_COUNT, CLAIMABLE_BALANCES,
+        COLLECTIONS, COLLECTION_MINTS, COLLECTION_MINT_LIMITS, COLLECTION_NAME_RULES,
+        COLLECTION_PAUSES, COLLECTION_PAYMENT_PARAMS, CONTRACT_NAME, CONTRACT_VERSION,
+        GLOBAL_PAUSE, NAME_EXPIRIES, PASS_THROUGH_COUNT, PASS_THROUGH_POLICIES, PAYMENT_PARAMS,
+        PENDING_BATCHES, PENDING_COLLECTION_LABEL, PENDING_PASS_THROUGHS, PHASE_MINT_COUNTS,
+        PHASE_WALLET_MINTS, ROLE_MEMBERS, WALLET_MINTS,
     },
-    state::{CONTRACT_NAME, CONTRACT_VERSION, PAYMENT_PARAMS},
 };
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    from_json, to_json_binary, BankMsg, Coin, CosmosMsg, Deps, DepsMut, Empty, Env, Event,
-    MessageInfo, QueryRequest, QueryResponse, Reply, ReplyOn, Response, StdError, SubMsg, Uint128,
-    WasmMsg, WasmQuery,
+    from_json, instantiate2_address, to_json_binary, Addr, Attribute, BankMsg, Binary, Coin,
+    CosmosMsg, Deps, DepsMut, Empty, Env, Event, HexBinary, MessageInfo, Order, QueryRequest,
+    QueryResponse, Reply, ReplyOn, Response, StdError, StdResult, Storage, SubMsg, SubMsgResult,
+    Timestamp, Uint128, WasmMsg, WasmQuery,
 };
 use cw2::{get_contract_version, set_contract_version, ContractVersion, VersionError};
-use cw721::msg::NumTokensResponse;
+use cw20::{Cw20CoinVerified, Cw20ExecuteMsg, Cw20ReceiveMsg};
+use cw721::msg::{NumTokensResponse, OperatorResponse, OwnerOfResponse};
+use cw_ownable::{
+    assert_owner, get_ownership, initialize_owner, is_owner, update_ownership, Action,
+};
+use cw_storage_plus::Bound;
+use cw_utils::{nonpayable, parse_execute_response_data, parse_reply_instantiate_data};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
 
 type ContractResult = Result<Response, ContractError>;
 
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
+#[repr(u64)]
 enum ReplyCode {
     PassThrough = 1,
+    CreateCollection = 2,
+    /// The items of a best-effort batch reply with this id plus their batch number times
+    /// `MAX_BATCH_ITEMS`, plus their index.
+    BatchItem = 1_000,
+    /// The pass-throughs held until their reply use this id plus their number.
+    PendingPassThrough = 1 << 63,
 }
 
 impl TryFrom<u64> for ReplyCode {
@@ -29,19 +67,30 @@ impl TryFrom<u64> for ReplyCode {
     fn try_from(item: u64) -> Result<Self, Self::Error> {
         match item {
             1 => Ok(ReplyCode::PassThrough),
+            2 => Ok(ReplyCode::CreateCollection),
+            id if id >= ReplyCode::PendingPassThrough as u64 => Ok(ReplyCode::PendingPassThrough),
+            id if id >= ReplyCode::BatchItem as u64 => Ok(ReplyCode::BatchItem),
             _ => panic!("invalid ReplyCode({})", item),
         }
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn instantiate(deps: DepsMut, _: Env, _: MessageInfo, msg: InstantiateMsg) -> ContractResult {
+pub fn instantiate(
+    deps: DepsMut,
+    _: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> ContractResult {
     msg.payment_params.validate()?;
     PAYMENT_PARAMS.save(deps.storage, &msg.payment_params)?;
+    let owner = msg.owner.unwrap_or_else(|| info.sender.to_string());
+    let ownership = initialize_owner(deps.storage, deps.api, Some(&owner))?;
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     let instantiate_event = Event::new("my-collection-manager")
         .add_attribute("update-contract-version", CONTRACT_VERSION);
     let instantiate_event = append_payment_params_attributes(instantiate_event, msg.payment_params);
+    let instantiate_event = append_owner_attribute(instantiate_event, ownership.owner);
     Ok(Response::default().add_event(instantiate_event))
 }
 
@@ -51,418 +100,5469 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> C
         ExecuteMsg::PassThrough {
             collection,
             message,
-        } => execute_pass_through(deps, env, info, collection, message),
+            options,
+        } => execute_pass_through(deps, env, info, collection, message, options),
+        ExecuteMsg::PassThroughBatch { items, mode } => {
+            execute_pass_through_batch(deps, env, info, items, mode)
+        }
+        ExecuteMsg::CreateCollection {
+            code_id,
+            label,
+            name,
+            symbol,
+            creator,
+            withdraw_address,
+            salt,
+        } => {
+            let instantiate_msg = CollectionInstantiateMsg {
+                name,
+                symbol,
+                collection_info_extension: None,
+                minter: Some(env.contract.address.to_string()),
+                creator,
+                withdraw_address,
+            };
+            execute_create_collection(deps, info, code_id, label, instantiate_msg, salt)
+        }
+        ExecuteMsg::UpdatePaymentParams(payment_params) => {
+            assert_owner_or_role(deps.storage, &info.sender, Role::PaymentManager)?;
+            sudo_update_payment_params(deps, payment_params)
+        }
+        ExecuteMsg::UpdateCollectionPaymentParams {
+            collection,
+            payment_params,
+        } => {
+            assert_owner_or_role(deps.storage, &info.sender, Role::PaymentManager)?;
+            sudo_update_collection_payment_params(deps, collection, payment_params)
+        }
+        ExecuteMsg::ClearCollectionPaymentParams { collection } => {
+            assert_owner_or_role(deps.storage, &info.sender, Role::PaymentManager)?;
+            sudo_clear_collection_payment_params(deps, collection)
+        }
+        ExecuteMsg::SetMintLimits { collection, limits } => {
+            assert_owner(deps.storage, &info.sender)?;
+            sudo_set_mint_limits(deps, collection, limits)
+        }
+        ExecuteMsg::SetNameRules { collection, rules } => {
+            assert_owner(deps.storage, &info.sender)?;
+            sudo_set_name_rules(deps, collection, rules)
+        }
+        ExecuteMsg::ClearNameRules { collection } => {
+            assert_owner(deps.storage, &info.sender)?;
+            sudo_clear_name_rules(deps, collection)
+        }
+        ExecuteMsg::RegisterCollection { collection, label } => {
+            assert_owner(deps.storage, &info.sender)?;
+            sudo_register_collection(deps, env, collection, label)
+        }
+        ExecuteMsg::UnregisterCollection { collection } => {
+            assert_owner(deps.storage, &info.sender)?;
+            sudo_unregister_collection(deps, collection)
+        }
+        ExecuteMsg::UpdateOwnership(action) => execute_update_ownership(deps, env, info, action),
+        ExecuteMsg::GrantRole { role, member } => execute_grant_role(deps, info, role, member),
+        ExecuteMsg::RevokeRole { role, member } => execute_revoke_role(deps, info, role, member),
+        ExecuteMsg::RenounceRole { role } => execute_renounce_role(deps, info, role),
+        ExecuteMsg::SetPassThroughPolicy { kind, policy } => {
+            execute_set_pass_through_policy(deps, info, kind, policy)
+        }
+        ExecuteMsg::Pause {
+            collection,
+            until_height,
+        } => {
+            assert_owner_or_role(deps.storage, &info.sender, Role::Pauser)?;
+            sudo_pause(deps, env, collection, until_height)
+        }
+        ExecuteMsg::Unpause { collection } => {
+            assert_owner_or_role(deps.storage, &info.sender, Role::Pauser)?;
+            sudo_unpause(deps, collection)
+        }
+        ExecuteMsg::Withdraw { denom, amount } => execute_withdraw(deps, info, denom, amount),
+        ExecuteMsg::Receive(receive_msg) => execute_receive(deps, env, info, receive_msg),
+        ExecuteMsg::SettleAuction { collection } => {
+            assert_owner_or_role(deps.storage, &info.sender, Role::Treasurer)?;
+            sudo_settle_auction(deps, collection)
+        }
+        ExecuteMsg::ClaimRebate { collection } => execute_claim_rebate(deps, info, collection),
+        ExecuteMsg::Renew {
+            collection,
+            token_id,
+            years,
+        } => execute_renew(deps, env, info, collection, token_id, years),
+        ExecuteMsg::ReclaimExpired {
+            collection,
+            token_id,
+        } => {
+            assert_owner_or_role(deps.storage, &info.sender, Role::Keeper)?;
+            execute_reclaim_expired(deps, env, info, collection, token_id)
+        }
     }
 }
 
-fn execute_pass_through(
+fn execute_withdraw(
     deps: DepsMut,
-    _: Env,
     info: MessageInfo,
-    collection: String,
-    message: CollectionExecuteMsg,
+    denom: String,
+    amount: Uint128,
 ) -> ContractResult {
-    let response = Response::default();
-    let response = match message {
-        CollectionExecuteMsg::Mint { .. } => match handle_pre_mint_funds(&deps, &info) {
-            Err(err) => Err(err)?,
-            Ok(bank_msgs) => response.add_messages(bank_msgs),
-        },
-        _ => {
-            if !info.funds.is_empty() {
-                let refund_msg = BankMsg::Send {
-                    to_address: info.sender.to_string(),
-                    amount: info.funds,
-                };
-                response.add_message(refund_msg)
-            } else {
-                response
-            }
-        }
-    };
-    let onward_exec_msg = WasmMsg::Execute {
-        contract_addr: collection.to_owned(),
-        msg: to_json_binary(&message)?,
-        funds: vec![],
-    };
-    let onward_sub_msg = SubMsg {
-        id: ReplyCode::PassThrough as u64,
-        msg: CosmosMsg::<Empty>::Wasm(onward_exec_msg),
-        reply_on: ReplyOn::Success,
-        gas_limit: None,
-    };
-    let token_count_result =
-        deps.querier
-            .query::<NumTokensResponse>(&QueryRequest::Wasm(WasmQuery::Smart {
-                contract_addr: collection,
-                msg: to_json_binary(&CollectionQueryMsg::NumTokens {})?,
-            }));
-    let token_count_event = Event::new("my-collection-manager")
-        .add_attribute("token-count-before", token_count_result?.count.to_string());
-    Ok(response
-        .add_submessage(onward_sub_msg)
-        .add_event(token_count_event))
+    nonpayable(&info)?;
+    if amount.is_zero() {
+        return Err(ContractError::ZeroAmount);
+    }
+    let balance = CLAIMABLE_BALANCES
+        .may_load(deps.storage, (&info.sender, denom.as_str()))?
+        .unwrap_or_default();
+    let remaining =
+        balance
+            .checked_sub(amount)
+            .map_err(|_| ContractError::InsufficientBalance {
+                available: Coin {
+                    denom: denom.to_owned(),
+                    amount: balance,
+                },
+            })?;
+    if remaining.is_zero() {
+        CLAIMABLE_BALANCES.remove(deps.storage, (&info.sender, denom.as_str()));
+    } else {
+        CLAIMABLE_BALANCES.save(deps.storage, (&info.sender, denom.as_str()), &remaining)?;
+    }
+    let withdraw_event = Event::new("my-collection-manager")
+        .add_attribute("withdraw-beneficiary", info.sender.to_owned())
+        .add_attribute("withdraw-denom", denom.to_owned())
+        .add_attribute("withdraw-amount", amount.to_string());
+    Ok(Response::default()
+        .add_message(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: vec![Coin { denom, amount }],
+        })
+        .add_event(withdraw_event))
 }
 
-fn handle_pre_mint_funds(
-    deps: &DepsMut,
-    info: &MessageInfo,
-) -> Result<Vec<BankMsg>, ContractError> {
-    let payment_params = PAYMENT_PARAMS.load(deps.storage)?;
-    let (payment, change) = match payment_params.mint_price {
-        None => (None, info.funds.to_owned()),
-        Some(minting_price) if minting_price.amount.le(&Uint128::zero()) => {
-            Err(ContractError::ZeroPrice)?
+fn execute_claim_rebate(deps: DepsMut, info: MessageInfo, collection: String) -> ContractResult {
+    nonpayable(&info)?;
+    let collection = deps.api.addr_validate(&collection)?;
+    let purchases = AUCTION_PURCHASES
+        .prefix((&collection, &info.sender))
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    if purchases.is_empty() {
+        return Err(ContractError::NoRebate);
+    }
+    // Claims the rebates of all the settled auctions at once, leaving the open one for later.
+    let mut settled_purchases = 0;
+    let mut rebates = Vec::<Coin>::new();
+    for (auction, purchase) in purchases {
+        let Some(sales) = AUCTION_SALES
+            .may_load(deps.storage, (&collection, auction))?
+            .filter(|sales| sales.settled)
+        else {
+            continue;
+        };
+        settled_purchases += 1;
+        AUCTION_PURCHASES.remove(deps.storage, (&collection, &info.sender, auction));
+        let cost = sales
+            .last_price
+            .amount
+            .checked_mul(Uint128::from(purchase.minted))
+            .map_err(StdError::from)?;
+        let rebate = purchase.paid.checked_sub(cost).map_err(StdError::from)?;
+        if rebate.is_zero() {
+            continue;
         }
-        Some(minting_price) => {
-            let (aggregated, mut others) = split_fund_denom(&minting_price.denom, &info.funds);
-            match aggregated.checked_sub(minting_price.amount) {
-                Err(_) => Err(ContractError::MissingPayment {
-                    missing_payment: minting_price.to_owned(),
-                })?,
-                Ok(change_in_denom) if change_in_denom.le(&Uint128::zero()) => {}
-                Ok(change_in_denom) => others.push(Coin {
-                    denom: minting_price.denom.clone(),
-                    amount: change_in_denom,
-                }),
-            };
-            (Some(minting_price), others)
+        match rebates
+            .iter_mut()
+            .find(|coin| coin.denom == sales.last_price.denom)
+        {
+            Some(coin) => coin.amount = coin.amount.checked_add(rebate).map_err(StdError::from)?,
+            None => rebates.push(Coin {
+                denom: sales.last_price.denom,
+                amount: rebate,
+            }),
         }
+    }
+    if settled_purchases == 0 {
+        return Err(ContractError::AuctionNotSettled {
+            collection: collection.to_string(),
+        });
+    }
+    if rebates.is_empty() {
+        return Err(ContractError::NoRebate);
+    }
+    rebates.sort_by(|a, b| a.denom.cmp(&b.denom));
+    let claim_event = rebates.iter().fold(
+        Event::new("my-collection-manager")
+            .add_attribute("claim-rebate-buyer", info.sender.to_owned()),
+        |claim_event, rebate| {
+            claim_event
+                .add_attribute("claim-rebate-denom", rebate.denom.to_owned())
+                .add_attribute("claim-rebate-amount", rebate.amount.to_string())
+        },
+    );
+    Ok(Response::default()
+        .add_message(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: rebates,
+        })
+        .add_event(claim_event))
+}
+
+fn execute_renew(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    collection: String,
+    token_id: String,
+    years: u32,
+) -> ContractResult {
+    let collection = deps.api.addr_validate(&collection)?;
+    if !COLLECTIONS.has(deps.storage, &collection) {
+        return Err(ContractError::CollectionNotRegistered {
+            collection: collection.to_string(),
+        });
+    }
+    // Renewing pays like minting, so it is paused alongside it.
+    if is_paused(deps.storage, &env, &collection)? {
+        return Err(ContractError::Paused {
+            collection: collection.to_string(),
+        });
+    }
+    let payment_params = load_payment_params(deps.storage, &collection)?;
+    let subscription =
+        payment_params
+            .subscription
+            .as_ref()
+            .ok_or_else(|| ContractError::NoSubscription {
+                collection: collection.to_string(),
+            })?;
+    subscription.validate_years(years)?;
+    let expires_at = NAME_EXPIRIES
+        .may_load(deps.storage, (&collection, token_id.as_str()))?
+        .ok_or_else(|| ContractError::NameNotRegistered {
+            name: token_id.to_owned(),
+        })?;
+    // The collection may have burned the name without the manager, leaving nothing to renew.
+    deps.querier
+        .query_wasm_smart::<OwnerOfResponse>(
+            &collection,
+            &CollectionQueryMsg::OwnerOf {
+                token_id: token_id.to_owned(),
+                include_expired: None,
+            },
+        )
+        .map_err(|_| ContractError::NameNotRegistered {
+            name: token_id.to_owned(),
+        })?;
+    // Past the grace period, the years paid for only start now, as the name was not reclaimed.
+    let renewed_from = if env.block.time > expires_at.plus_seconds(subscription.grace_period) {
+        env.block.time
+    } else {
+        expires_at
     };
+    let expires_at = renewed_from.plus_seconds(u64::from(years) * SECONDS_PER_YEAR);
+    NAME_EXPIRIES.save(deps.storage, (&collection, token_id.as_str()), &expires_at)?;
+    let token_count = query_token_count(deps.as_ref(), &collection)?;
+    let renewal_prices = multiply_prices(
+        base_mint_prices(&payment_params, &env, token_count, Some(&token_id))?,
+        years.into(),
+    )?;
+    let (payment, change) = take_payment(&payment_params, &info.funds, renewal_prices)?;
+    let mut renew_event = Event::new("my-collection-manager")
+        .add_attribute("renew-collection", collection)
+        .add_attribute("renew-name", token_id)
+        .add_attribute("renew-expires-at", expires_at.seconds().to_string());
     let mut bank_msgs = Vec::<BankMsg>::new();
     if let Some(paid) = payment {
-        bank_msgs.push(BankMsg::Send {
-            to_address: payment_params.beneficiary.to_string(),
-            amount: vec![paid],
-        });
+        renew_event = renew_event
+            .add_attribute("renew-payment-denom", paid.denom.to_owned())
+            .add_attribute("renew-payment-amount", paid.amount.to_string());
+        let (payout_msgs, payout_attributes) =
+            pay_beneficiaries(deps.storage, &payment_params, &paid)?;
+        bank_msgs.extend(payout_msgs);
+        renew_event = renew_event.add_attributes(payout_attributes);
     }
     if !change.is_empty() {
         bank_msgs.push(BankMsg::Send {
             to_address: info.sender.to_string(),
             amount: change,
-        })
-    };
-    Ok(bank_msgs)
+        });
+    }
+    Ok(Response::default()
+        .add_messages(bank_msgs)
+        .add_event(renew_event))
 }
 
-fn split_fund_denom(denom: &String, funds: &[Coin]) -> (Uint128, Vec<Coin>) {
-    let (amount, others) = funds.iter().fold(
-        (Uint128::zero(), Vec::with_capacity(funds.len())),
-        |(aggregated, mut others), fund| {
-            if &fund.denom == denom {
-                (aggregated.strict_add(fund.amount), others)
-            } else {
-                others.push(fund.clone());
-                (aggregated, others)
-            }
+/// Burns the name through the collection, where its owner must have approved the manager, for the
+/// token or as operator, since only those may burn it. Until then, the name can still be renewed.
+fn execute_reclaim_expired(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    collection: String,
+    token_id: String,
+) -> ContractResult {
+    nonpayable(&info)?;
+    let collection = deps.api.addr_validate(&collection)?;
+    if !COLLECTIONS.has(deps.storage, &collection) {
+        return Err(ContractError::CollectionNotRegistered {
+            collection: collection.to_string(),
+        });
+    }
+    let expires_at = NAME_EXPIRIES
+        .may_load(deps.storage, (&collection, token_id.as_str()))?
+        .ok_or_else(|| ContractError::NameNotRegistered {
+            name: token_id.to_owned(),
+        })?;
+    let grace_period = load_payment_params(deps.storage, &collection)?
+        .subscription
+        .map_or(0, |subscription| subscription.grace_period);
+    let grace_ends_at = expires_at.plus_seconds(grace_period);
+    if env.block.time <= grace_ends_at {
+        return Err(ContractError::NameNotExpired {
+            name: token_id,
+            grace_ends_at,
+        });
+    }
+    if !is_approved_for(deps.as_ref(), &env, &collection, &token_id)? {
+        return Err(ContractError::ReclaimNotApproved { name: token_id });
+    }
+    NAME_EXPIRIES.remove(deps.storage, (&collection, token_id.as_str()));
+    let token_count = query_token_count(deps.as_ref(), &collection)?;
+    forward_pass_through(
+        Response::default(),
+        &collection,
+        &CollectionExecuteMsg::Burn {
+            token_id: token_id.to_owned(),
         },
-    );
-    (amount, others)
+        token_count,
+        vec![Attribute::new("reclaim-expired", token_id)],
+        ReplyCode::PassThrough as u64,
+        ReplyOn::Success,
+    )
 }
 
-#[cfg_attr(not(feature = "library"), entry_point)]
-pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> ContractResult {
-    match ReplyCode::try_from(msg.id)? {
-        ReplyCode::PassThrough => reply_pass_through(deps, env, msg),
+fn is_approved_for(deps: Deps, env: &Env, collection: &Addr, token_id: &str) -> StdResult<bool> {
+    let manager = env.contract.address.as_str();
+    let owner_of: OwnerOfResponse = deps.querier.query_wasm_smart(
+        collection,
+        &CollectionQueryMsg::OwnerOf {
+            token_id: token_id.to_owned(),
+            include_expired: Some(false),
+        },
+    )?;
+    if owner_of
+        .approvals
+        .iter()
+        .any(|approval| approval.spender.as_str() == manager)
+    {
+        return Ok(true);
     }
+    // Errors when the manager is not an operator of the owner.
+    let operator = deps.querier.query_wasm_smart::<OperatorResponse>(
+        collection,
+        &CollectionQueryMsg::Operator {
+            owner: owner_of.owner.to_string(),
+            operator: manager.to_owned(),
+            include_expired: Some(false),
+        },
+    );
+    Ok(operator.is_ok())
 }
 
-fn reply_pass_through(_deps: DepsMut, _env: Env, msg: Reply) -> ContractResult {
-    let resp = msg.result.into_result().map_err(StdError::generic_err)?;
-    let data = if let Some(data) = resp.data {
-        data.0[2..].to_vec()
-    } else {
-        return Ok(Response::default());
-    };
-    let value = if let Ok(value) = from_json::<NameServiceExecuteMsgResponse>(data) {
-        value
-    } else {
-        return Ok(Response::default());
-    };
-    let event = Event::new("my-collection-manager")
-        .add_attribute("token-count-after", value.num_tokens.to_string());
-    Ok(Response::default().add_event(event))
+fn execute_update_ownership(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    action: Action,
+) -> ContractResult {
+    let ownership = update_ownership(deps, &env.block, &info.sender, action)?;
+    let ownership_event =
+        Event::new("my-collection-manager").add_attributes(ownership.into_attributes());
+    Ok(Response::default().add_event(ownership_event))
 }
 
-#[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> Result<QueryResponse, ContractError> {
-    match msg {
-        QueryMsg::GetPaymentParams {} => Ok(to_json_binary(&GetPaymentParamsResponse {
-            payment_params: PAYMENT_PARAMS.load(deps.storage)?,
-        })?),
-    }
+fn execute_grant_role(
+    deps: DepsMut,
+    info: MessageInfo,
+    role: Role,
+    member: String,
+) -> ContractResult {
+    assert_owner(deps.storage, &info.sender)?;
+    let member = deps.api.addr_validate(&member)?;
+    ROLE_MEMBERS.save(deps.storage, (role.as_str(), &member), &Empty {})?;
+    let role_event = Event::new("my-collection-manager")
+        .add_attribute("grant-role", role.as_str())
+        .add_attribute("grant-role-member", member);
+    Ok(Response::default().add_event(role_event))
 }
 
-#[cfg_attr(not(feature = "library"), entry_point)]
-pub fn sudo(deps: DepsMut, _env: Env, msg: SudoMsg) -> ContractResult {
-    match msg {
-        SudoMsg::UpdatePaymentParams(payment_params) => {
-            sudo_update_payment_params(deps, payment_params)
-        }
+fn execute_revoke_role(
+    deps: DepsMut,
+    info: MessageInfo,
+    role: Role,
+    member: String,
+) -> ContractResult {
+    assert_owner(deps.storage, &info.sender)?;
+    let member = deps.api.addr_validate(&member)?;
+    ROLE_MEMBERS.remove(deps.storage, (role.as_str(), &member));
+    let role_event = Event::new("my-collection-manager")
+        .add_attribute("revoke-role", role.as_str())
+        .add_attribute("revoke-role-member", member);
+    Ok(Response::default().add_event(role_event))
+}
+
+fn execute_renounce_role(deps: DepsMut, info: MessageInfo, role: Role) -> ContractResult {
+    if !ROLE_MEMBERS.has(deps.storage, (role.as_str(), &info.sender)) {
+        return Err(ContractError::MissingRole {
+            sender: info.sender.to_string(),
+            role,
+        });
     }
+    ROLE_MEMBERS.remove(deps.storage, (role.as_str(), &info.sender));
+    let role_event = Event::new("my-collection-manager")
+        .add_attribute("renounce-role", role.as_str())
+        .add_attribute("renounce-role-member", info.sender);
+    Ok(Response::default().add_event(role_event))
 }
 
-fn sudo_update_payment_params(deps: DepsMut, payment_params: PaymentParams) -> ContractResult {
-    payment_params.validate()?;
-    PAYMENT_PARAMS.save(deps.storage, &payment_params)?;
-    let sudo_event = Event::new("my-collection-manager");
-    let sudo_event = append_payment_params_attributes(sudo_event, payment_params);
-    Ok(Response::default().add_event(sudo_event))
+fn execute_set_pass_through_policy(
+    deps: DepsMut,
+    info: MessageInfo,
+    kind: CollectionMsgKind,
+    policy: PassThroughPolicy,
+) -> ContractResult {
+    assert_owner(deps.storage, &info.sender)?;
+    PASS_THROUGH_POLICIES.save(deps.storage, kind.as_str(), &policy)?;
+    let policy_event = Event::new("my-collection-manager")
+        .add_attribute("update-pass-through-policy-kind", kind.as_str())
+        .add_attribute("update-pass-through-policy", policy.to_attribute_value());
+    Ok(Response::default().add_event(policy_event))
 }
 
-#[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> ContractResult {
-    if let Ok(ContractVersion {
-        contract: _,
-        version,
-    }) = get_contract_version(deps.storage)
-    {
-        return Err(ContractError::Version(VersionError::WrongVersion {
-            expected: "0.0.0".to_owned(),
-            found: version,
-        }));
-    }
-    msg.payment_params.validate()?;
-    PAYMENT_PARAMS.save(deps.storage, &msg.payment_params)?;
-    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
-    let migrate_event = Event::new("my-collection-manager")
-        .add_attribute("update-contract-version", CONTRACT_VERSION);
-    let migrate_event = append_payment_params_attributes(migrate_event, msg.payment_params);
-    Ok(Response::default().add_event(migrate_event))
+fn load_pass_through_policy(
+    storage: &dyn Storage,
+    kind: CollectionMsgKind,
+) -> StdResult<PassThroughPolicy> {
+    Ok(PASS_THROUGH_POLICIES
+        .may_load(storage, kind.as_str())?
+        .unwrap_or_else(|| kind.default_policy()))
 }
 
-fn append_payment_params_attributes(my_event: Event, payment_params: PaymentParams) -> Event {
-    let my_event = my_event.add_attribute(
-        "update-payment-params-beneficiary",
-        payment_params.beneficiary,
-    );
-    match payment_params.mint_price {
-        None => my_event.add_attribute("update-payment-params-mint-price", "none"),
-        Some(mint_price) => my_event
-            .add_attribute("update-payment-params-mint-price-denom", mint_price.denom)
-            .add_attribute(
-                "update-payment-params-mint-price-amount",
-                mint_price.amount.to_string(),
-            ),
+fn assert_owner_or_role(
+    storage: &dyn Storage,
+    sender: &Addr,
+    role: Role,
+) -> Result<(), ContractError> {
+    if ROLE_MEMBERS.has(storage, (role.as_str(), sender)) || is_owner(storage, sender)? {
+        Ok(())
+    } else {
+        Err(ContractError::MissingRole {
+            sender: sender.to_string(),
+            role,
+        })
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::{
-        contract::ReplyCode,
-        msg::{
-            CollectionExecuteMsg, CollectionQueryMsg, ExecuteMsg, InstantiateMsg, MigrateMsg,
-            NameServiceExecuteMsgResponse, PaymentParams, SudoMsg,
+fn execute_create_collection(
+    deps: DepsMut,
+    info: MessageInfo,
+    code_id: u64,
+    label: String,
+    instantiate_msg: CollectionInstantiateMsg,
+    salt: Option<Binary>,
+) -> ContractResult {
+    assert_owner(deps.storage, &info.sender)?;
+    PENDING_COLLECTION_LABEL.save(deps.storage, &label)?;
+    let instantiate_wasm_msg = match salt {
+        None => WasmMsg::Instantiate {
+            admin: None,
+            code_id,
+            msg: to_json_binary(&instantiate_msg)?,
+            funds: vec![],
+            label,
+        },
+        Some(salt) => WasmMsg::Instantiate2 {
+            admin: None,
+            code_id,
+            label,
+            msg: to_json_binary(&instantiate_msg)?,
+            funds: vec![],
+            salt,
         },
-        state::PAYMENT_PARAMS,
     };
-    use cosmwasm_std::{
-        from_json,
-        testing::{self, MockApi, MockQuerier, MockStorage},
-        to_json_binary, Addr, BankMsg, Binary, Coin, ContractResult, CosmosMsg, Empty, Event,
-        OwnedDeps, Querier, QuerierResult, QueryRequest, Reply, ReplyOn, Response, SubMsg,
-        SubMsgResponse, SubMsgResult, SystemError, SystemResult, Uint128, WasmMsg, WasmQuery,
+    let instantiate_sub_msg = SubMsg {
+        id: ReplyCode::CreateCollection as u64,
+        msg: CosmosMsg::<Empty>::Wasm(instantiate_wasm_msg),
+        reply_on: ReplyOn::Success,
+        gas_limit: None,
     };
-    use cw2::{assert_contract_version, ContractVersion};
-    use cw721::msg::NumTokensResponse;
-    use std::marker::PhantomData;
+    Ok(Response::default().add_submessage(instantiate_sub_msg))
+}
 
-    pub fn mock_deps(
-        response: NumTokensResponse,
-    ) -> OwnedDeps<MockStorage, MockApi, NumTokensMockQuerier, Empty> {
-        OwnedDeps {
-            storage: MockStorage::default(),
-            api: MockApi::default(),
-            querier: NumTokensMockQuerier::new(MockQuerier::new(&[]), response),
-            custom_query_type: PhantomData,
+fn execute_pass_through(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    collection: String,
+    message: CollectionExecuteMsg,
+    options: Option<PassThroughOptions>,
+) -> ContractResult {
+    let options = options.unwrap_or_default();
+    let collection =
+        authorize_pass_through(deps.as_ref(), &env, &info.sender, collection, &message)?;
+    let token_count = query_token_count(deps.as_ref(), &collection)?;
+    let response = Response::default();
+    let mut pending_mint = None;
+    let (response, mint_attributes) = match &message {
+        CollectionExecuteMsg::Mint { token_id, .. } => {
+            use_mint_limits(deps.storage, &collection, &info.sender, token_count)?;
+            let (mint, mut mint_attributes, change) = price_mint(
+                &mut deps,
+                &env,
+                &info,
+                &collection,
+                token_count,
+                token_id,
+                &options,
+            )?;
+            let mut bank_msgs = Vec::<BankMsg>::new();
+            if options.catch_errors {
+                // Paid out or refunded once the collection replies.
+                pending_mint = Some(mint);
+            } else {
+                let (payout_msgs, payout_attributes) =
+                    settle_mint_payment(deps.storage, &collection, &info.sender, &mint)?;
+                bank_msgs.extend(payout_msgs);
+                mint_attributes.extend(payout_attributes);
+            }
+            if !change.is_empty() {
+                bank_msgs.push(BankMsg::Send {
+                    to_address: info.sender.to_string(),
+                    amount: change,
+                });
+            }
+            (response.add_messages(bank_msgs), mint_attributes)
         }
+        _ => {
+            if !info.funds.is_empty() {
+                let refund_msg = BankMsg::Send {
+                    to_address: info.sender.to_string(),
+                    amount: info.funds,
+                };
+                (response.add_message(refund_msg), vec![])
+            } else {
+                (response, vec![])
+            }
+        }
+    };
+    let mut reply_id = ReplyCode::PassThrough as u64;
+    if options.catch_errors || options.return_data.is_some() {
+        let pass_through_number = PASS_THROUGH_COUNT
+            .may_load(deps.storage)?
+            .unwrap_or_default();
+        PASS_THROUGH_COUNT.save(deps.storage, &(pass_through_number + 1))?;
+        reply_id = ReplyCode::PendingPassThrough as u64 + pass_through_number;
+        PENDING_PASS_THROUGHS.save(
+            deps.storage,
+            reply_id,
+            &PendingPassThrough {
+                minter: info.sender,
+                collection: collection.to_owned(),
+                kind: CollectionMsgKind::from(&message),
+                mint: pending_mint,
+                token_count_before: token_count,
+                return_data: options.return_data,
+            },
+        )?;
     }
+    let reply_on = if options.catch_errors {
+        ReplyOn::Always
+    } else {
+        ReplyOn::Success
+    };
+    forward_pass_through(
+        response,
+        &collection,
+        &message,
+        token_count,
+        mint_attributes,
+        reply_id,
+        reply_on,
+    )
+}
 
-    pub struct NumTokensMockQuerier {
-        base: MockQuerier,
-        response: NumTokensResponse,
+fn execute_pass_through_batch(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    items: Vec<PassThroughItem>,
+    mode: BatchMode,
+) -> ContractResult {
+    if items.is_empty() || items.len() > MAX_BATCH_ITEMS {
+        return Err(ContractError::InvalidBatchSize {
+            max_items: MAX_BATCH_ITEMS,
+        });
     }
-
-    impl Querier for NumTokensMockQuerier {
-        fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
-            let request: QueryRequest<Empty> = match from_json(bin_request) {
-                Ok(v) => v,
-                Err(e) => {
-                    return SystemResult::Err(SystemError::InvalidRequest {
-                        error: format!("Parsing query request: {}", e),
-                        request: bin_request.into(),
-                    })
-                }
+    let best_effort = matches!(mode, BatchMode::BestEffort);
+    let batch_number = BATCH_COUNT.may_load(deps.storage)?.unwrap_or_default();
+    let mut pending_items = Vec::<PendingBatchItem>::new();
+    let mut remaining_funds = info.funds.to_owned();
+    let mut bank_msgs = Vec::<BankMsg>::new();
+    let mut sub_msgs = Vec::<SubMsg>::with_capacity(items.len());
+    let mut batch_event = Event::new("my-collection-manager");
+    // The collections only mint once the whole batch is priced, so that earlier mints of the
+    // batch have to be added to their token count.
+    let mut batch_mints = BTreeMap::<Addr, u64>::new();
+    for (index, item) in items.into_iter().enumerate() {
+        let options = item.options.unwrap_or_default();
+        let collection = authorize_pass_through(
+            deps.as_ref(),
+            &env,
+            &info.sender,
+            item.collection,
+            &item.message,
+        )?;
+        let batch_minted = batch_mints.entry(collection.to_owned()).or_default();
+        let token_count = query_token_count(deps.as_ref(), &collection)? + *batch_minted;
+        let mut item_attributes = vec![
+            Attribute::new("batch-item", index.to_string()),
+            Attribute::new("batch-item-collection", collection.to_owned()),
+            Attribute::new("token-count-before", token_count.to_string()),
+        ];
+        let mut pending_mint = None;
+        if let CollectionExecuteMsg::Mint { token_id, .. } = &item.message {
+            use_mint_limits(deps.storage, &collection, &info.sender, token_count)?;
+            let item_info = MessageInfo {
+                sender: info.sender.to_owned(),
+                funds: remaining_funds,
             };
+            let (mint, mint_attributes, change) = price_mint(
+                &mut deps,
+                &env,
+                &item_info,
+                &collection,
+                token_count,
+                token_id,
+                &options,
+            )?;
+            item_attributes.extend(mint_attributes);
+            if best_effort {
+                // Paid out or refunded once the collection replies.
+                pending_mint = Some(mint);
+            } else {
+                let (payout_msgs, payout_attributes) =
+                    settle_mint_payment(deps.storage, &collection, &info.sender, &mint)?;
+                bank_msgs.extend(payout_msgs);
+                item_attributes.extend(payout_attributes);
+            }
+            remaining_funds = change;
+            *batch_minted += 1;
+        }
+        batch_event = batch_event.add_attributes(item_attributes);
+        let sub_msg = pass_through_sub_msg(&collection, &item.message)?;
+        if best_effort {
+            sub_msgs.push(SubMsg {
+                id: ReplyCode::BatchItem as u64
+                    + batch_number * MAX_BATCH_ITEMS as u64
+                    + index as u64,
+                reply_on: ReplyOn::Always,
+                ..sub_msg
+            });
+            pending_items.push(PendingBatchItem {
+                collection,
+                mint: pending_mint,
+            });
+        } else {
+            sub_msgs.push(sub_msg);
+        }
+    }
+    if best_effort {
+        BATCH_COUNT.save(deps.storage, &(batch_number + 1))?;
+        PENDING_BATCHES.save(
+            deps.storage,
+            batch_number,
+            &PendingBatch {
+                minter: info.sender.to_owned(),
+                items: pending_items,
+                succeeded: vec![],
+                failed: vec![],
+            },
+        )?;
+    }
+    if !remaining_funds.is_empty() {
+        bank_msgs.push(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: remaining_funds,
+        });
+    }
+    Ok(Response::default()
+        .add_messages(bank_msgs)
+        .add_submessages(sub_msgs)
+        .add_event(batch_event))
+}
+
+/// Handles a mint passed through with a payment in CW20 tokens, sent by the token contract.
+fn execute_receive(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    receive_msg: Cw20ReceiveMsg,
+) -> ContractResult {
+    nonpayable(&info)?;
+    let ReceiveMsg::PassThrough {
+        collection,
+        message,
+        options,
+    } = from_json(&receive_msg.msg)?;
+    let options = options.unwrap_or_default();
+    let sender = deps.api.addr_validate(&receive_msg.sender)?;
+    let collection = authorize_pass_through(deps.as_ref(), &env, &sender, collection, &message)?;
+    let CollectionExecuteMsg::Mint { token_id, .. } = &message else {
+        return Err(ContractError::Cw20PaymentNotAccepted {
+            token: info.sender.to_string(),
+        });
+    };
+    let token_count = query_token_count(deps.as_ref(), &collection)?;
+    use_mint_limits(deps.storage, &collection, &sender, token_count)?;
+    let payment_params = load_payment_params(deps.storage, &collection)?;
+    let mut minting_price = match payment_params.cw20_mint_price.to_owned() {
+        Some(minting_price) if minting_price.address == info.sender => minting_price,
+        None | Some(_) => Err(ContractError::Cw20PaymentNotAccepted {
+            token: info.sender.to_string(),
+        })?,
+    };
+    let subscription = use_subscription(
+        deps.storage,
+        &env,
+        &collection,
+        token_id,
+        &payment_params,
+        &options,
+    )?;
+    if let Some((years, _, _)) = subscription {
+        minting_price.amount = minting_price
+            .amount
+            .checked_mul(Uint128::from(years))
+            .map_err(StdError::from)?;
+    }
+    let change = receive_msg
+        .amount
+        .checked_sub(minting_price.amount)
+        .map_err(|_| ContractError::MissingCw20Payment {
+            missing_payment: Cw20CoinVerified {
+                address: info.sender.to_owned(),
+                amount: minting_price.amount.strict_sub(receive_msg.amount),
+            },
+        })?;
+    let mut transfers = split_among_beneficiaries(&payment_params, minting_price.amount);
+    if !change.is_zero() {
+        transfers.push((sender, change));
+    }
+    let transfer_msgs = transfers
+        .into_iter()
+        .map(|(recipient, amount)| {
+            Ok(WasmMsg::Execute {
+                contract_addr: info.sender.to_string(),
+                msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: recipient.to_string(),
+                    amount,
+                })?,
+                funds: vec![],
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    let mut mint_attributes = Vec::<Attribute>::new();
+    if let Some((_, expires_at, _)) = subscription {
+        mint_attributes.push(Attribute::new(
+            "mint-expires-at",
+            expires_at.seconds().to_string(),
+        ));
+    }
+    mint_attributes.push(Attribute::new("mint-payment-cw20", info.sender.to_owned()));
+    mint_attributes.push(Attribute::new(
+        "mint-payment-amount",
+        minting_price.amount.to_string(),
+    ));
+    forward_pass_through(
+        Response::default().add_messages(transfer_msgs),
+        &collection,
+        &message,
+        token_count,
+        mint_attributes,
+        ReplyCode::PassThrough as u64,
+        ReplyOn::Success,
+    )
+}
+
+/// Checks that the sender may pass the message through to a registered collection.
+fn authorize_pass_through(
+    deps: Deps,
+    env: &Env,
+    sender: &Addr,
+    collection: String,
+    message: &CollectionExecuteMsg,
+) -> Result<Addr, ContractError> {
+    let collection = deps.api.addr_validate(&collection)?;
+    if !COLLECTIONS.has(deps.storage, &collection) {
+        return Err(ContractError::CollectionNotRegistered {
+            collection: collection.to_string(),
+        });
+    }
+    let kind = CollectionMsgKind::from(message);
+    match load_pass_through_policy(deps.storage, kind)? {
+        PassThroughPolicy::Anyone => {}
+        PassThroughPolicy::Role(role) => assert_owner_or_role(deps.storage, sender, role)?,
+        PassThroughPolicy::Forbidden => return Err(ContractError::PassThroughForbidden { kind }),
+    }
+    // Of the pass-throughs, only mints are paused, so that other messages keep being passed and
+    // their funds refunded.
+    if kind == CollectionMsgKind::Mint && is_paused(deps.storage, env, &collection)? {
+        return Err(ContractError::Paused {
+            collection: collection.to_string(),
+        });
+    }
+    if let CollectionExecuteMsg::Mint { token_id, .. } = message {
+        check_name_rules(deps.storage, &collection, sender, token_id)?;
+    }
+    Ok(collection)
+}
+
+fn check_name_rules(
+    storage: &dyn Storage,
+    collection: &Addr,
+    sender: &Addr,
+    name: &str,
+) -> Result<(), ContractError> {
+    let Some(rules) = COLLECTION_NAME_RULES.may_load(storage, collection)? else {
+        return Ok(());
+    };
+    rules.validate_name(name)?;
+    if rules.reserved_names.iter().any(|reserved| reserved == name)
+        && assert_owner_or_role(storage, sender, Role::Registrar).is_err()
+    {
+        return Err(ContractError::NameReserved {
+            name: name.to_owned(),
+        });
+    }
+    Ok(())
+}
+
+fn query_token_count(deps: Deps, collection: &Addr) -> StdResult<u64> {
+    let token_count_result =
+        deps.querier
+            .query::<NumTokensResponse>(&QueryRequest::Wasm(WasmQuery::Smart {
+                contract_addr: collection.to_string(),
+                msg: to_json_binary(&CollectionQueryMsg::NumTokens {})?,
+            }));
+    Ok(token_count_result?.count)
+}
+
+fn forward_pass_through(
+    response: Response,
+    collection: &Addr,
+    message: &CollectionExecuteMsg,
+    token_count: u64,
+    mint_attributes: Vec<Attribute>,
+    reply_id: u64,
+    reply_on: ReplyOn,
+) -> ContractResult {
+    let pass_through_event = Event::new("my-collection-manager")
+        .add_attribute("token-count-before", token_count.to_string())
+        .add_attributes(mint_attributes);
+    let sub_msg = SubMsg {
+        id: reply_id,
+        reply_on,
+        ..pass_through_sub_msg(collection, message)?
+    };
+    Ok(response
+        .add_submessage(sub_msg)
+        .add_event(pass_through_event))
+}
+
+fn pass_through_sub_msg(collection: &Addr, message: &CollectionExecuteMsg) -> StdResult<SubMsg> {
+    let onward_exec_msg = WasmMsg::Execute {
+        contract_addr: collection.to_string(),
+        msg: to_json_binary(message)?,
+        funds: vec![],
+    };
+    Ok(SubMsg {
+        id: ReplyCode::PassThrough as u64,
+        msg: CosmosMsg::<Empty>::Wasm(onward_exec_msg),
+        reply_on: ReplyOn::Success,
+        gas_limit: None,
+    })
+}
+
+fn is_paused(storage: &dyn Storage, env: &Env, collection: &Addr) -> StdResult<bool> {
+    let global_pause = GLOBAL_PAUSE.may_load(storage)?;
+    let collection_pause = COLLECTION_PAUSES.may_load(storage, collection)?;
+    Ok([global_pause, collection_pause]
+        .iter()
+        .flatten()
+        .any(|pause| pause.is_active(env.block.height)))
+}
+
+/// Counts the mint against its phase and takes the first of the mint prices that the funds fully
+/// cover, leaving the payment to `settle_mint_payment`. Also returns the change, left to the
+/// caller to refund.
+fn price_mint(
+    deps: &mut DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    collection: &Addr,
+    token_count: u64,
+    token_id: &str,
+    options: &PassThroughOptions,
+) -> Result<(PendingMint, Vec<Attribute>, Vec<Coin>), ContractError> {
+    let payment_params = load_payment_params(deps.storage, collection)?;
+    let mut mint_attributes = Vec::<Attribute>::new();
+    let phase = use_mint_phase(
+        deps.storage,
+        env,
+        collection,
+        &info.sender,
+        &payment_params,
+        options,
+    )?;
+    // Proceeds of an auction with a rebate are only paid out once the clearing price is known.
+    let escrow_auction = match &payment_params.pricing {
+        PricingRule::DutchAuction(auction) if auction.rebate && phase.is_none() => {
+            Some(auction.start_time.seconds())
+        }
+        _ => None,
+    };
+    let phase_name = phase.as_ref().map(|phase| phase.name.to_owned());
+    let mint_prices = match phase {
+        None => base_mint_prices(&payment_params, env, token_count, Some(token_id))?,
+        Some(phase) => {
+            mint_attributes.push(Attribute::new("mint-phase", phase.name));
+            phase.mint_prices
+        }
+    };
+    let subscription = use_subscription(
+        deps.storage,
+        env,
+        collection,
+        token_id,
+        &payment_params,
+        options,
+    )?;
+    let mint_prices = match subscription {
+        None => mint_prices,
+        Some((years, expires_at, _)) => {
+            mint_attributes.push(Attribute::new(
+                "mint-expires-at",
+                expires_at.seconds().to_string(),
+            ));
+            multiply_prices(mint_prices, years.into())?
+        }
+    };
+    let (payment, change) = take_payment(&payment_params, &info.funds, mint_prices)?;
+    if let Some(paid) = &payment {
+        mint_attributes.push(Attribute::new("mint-payment-denom", paid.denom.to_owned()));
+        mint_attributes.push(Attribute::new(
+            "mint-payment-amount",
+            paid.amount.to_string(),
+        ));
+    }
+    let mint = PendingMint {
+        token_id: token_id.to_owned(),
+        payment,
+        escrow_auction,
+        phase: phase_name,
+        subscribed: subscription.is_some(),
+        previous_expiry: subscription.and_then(|(_, _, previous_expiry)| previous_expiry),
+    };
+    Ok((mint, mint_attributes, change))
+}
+
+/// Pays the mint, either to the beneficiaries by sending or crediting, or into the auction
+/// escrow, and describes it in the returned attributes.
+fn settle_mint_payment(
+    storage: &mut dyn Storage,
+    collection: &Addr,
+    minter: &Addr,
+    mint: &PendingMint,
+) -> Result<(Vec<BankMsg>, Vec<Attribute>), ContractError> {
+    let Some(paid) = &mint.payment else {
+        return Ok((vec![], vec![]));
+    };
+    if let Some(auction) = mint.escrow_auction {
+        escrow_auction_purchase(storage, collection, auction, minter, paid)?;
+        return Ok((
+            vec![],
+            vec![Attribute::new("mint-payment-escrowed", "true")],
+        ));
+    }
+    let payment_params = load_payment_params(storage, collection)?;
+    Ok(pay_beneficiaries(storage, &payment_params, paid)?)
+}
+
+/// Gives back what a mint rejected by its collection counted against the limits, the phase and
+/// the subscriptions.
+fn undo_mint(
+    storage: &mut dyn Storage,
+    collection: &Addr,
+    minter: &Addr,
+    mint: &PendingMint,
+) -> StdResult<()> {
+    let uncount =
+        |count: Option<u64>| -> StdResult<u64> { Ok(count.unwrap_or_default().saturating_sub(1)) };
+    COLLECTION_MINTS.update(storage, collection, uncount)?;
+    WALLET_MINTS.update(storage, (collection, minter), uncount)?;
+    if let Some(phase) = &mint.phase {
+        PHASE_MINT_COUNTS.update(storage, (collection, phase.as_str()), uncount)?;
+        PHASE_WALLET_MINTS.update(storage, (collection, phase.as_str(), minter), uncount)?;
+    }
+    if mint.subscribed {
+        let key = (collection, mint.token_id.as_str());
+        match mint.previous_expiry {
+            Some(previous_expiry) => NAME_EXPIRIES.save(storage, key, &previous_expiry)?,
+            None => NAME_EXPIRIES.remove(storage, key),
+        }
+    }
+    Ok(())
+}
+
+/// Picks the first of the prices that the funds fully cover, and returns it with the change.
+fn take_payment(
+    payment_params: &PaymentParams,
+    funds: &[Coin],
+    prices: Vec<Coin>,
+) -> Result<(Option<Coin>, Vec<Coin>), ContractError> {
+    if prices.is_empty() {
+        // Only free when there is no CW20 price either.
+        if let Some(cw20_mint_price) = &payment_params.cw20_mint_price {
+            return Err(ContractError::Cw20PaymentRequired {
+                token: cw20_mint_price.address.to_string(),
+            });
+        }
+        return Ok((None, funds.to_owned()));
+    }
+    let mut missing_payment = Vec::with_capacity(prices.len());
+    for price in prices {
+        if price.amount.le(&Uint128::zero()) {
+            Err(ContractError::ZeroPrice)?
+        }
+        let (aggregated, mut others) = split_fund_denom(&price.denom, funds);
+        match aggregated.checked_sub(price.amount) {
+            Err(_) => missing_payment.push(Coin {
+                amount: price.amount.strict_sub(aggregated),
+                denom: price.denom,
+            }),
+            Ok(change_in_denom) => {
+                if !change_in_denom.is_zero() {
+                    others.push(Coin {
+                        denom: price.denom.clone(),
+                        amount: change_in_denom,
+                    });
+                }
+                return Ok((Some(price), others));
+            }
+        };
+    }
+    Err(ContractError::MissingPayment { missing_payment })
+}
+
+fn multiply_prices(prices: Vec<Coin>, factor: u64) -> StdResult<Vec<Coin>> {
+    prices
+        .into_iter()
+        .map(|price| {
+            Ok(Coin {
+                amount: price.amount.checked_mul(Uint128::from(factor))?,
+                denom: price.denom,
+            })
+        })
+        .collect()
+}
+
+/// Registers the name until the end of the years paid for, if the payment params have a
+/// subscription, and returns these years with the expiry.
+fn use_subscription(
+    storage: &mut dyn Storage,
+    env: &Env,
+    collection: &Addr,
+    token_id: &str,
+    payment_params: &PaymentParams,
+    options: &PassThroughOptions,
+) -> Result<Option<(u32, Timestamp, Option<Timestamp>)>, ContractError> {
+    let Some(subscription) = &payment_params.subscription else {
+        return Ok(None);
+    };
+    let years = options.years.unwrap_or(1);
+    subscription.validate_years(years)?;
+    let expires_at = env
+        .block
+        .time
+        .plus_seconds(u64::from(years) * SECONDS_PER_YEAR);
+    let previous_expiry = NAME_EXPIRIES.may_load(storage, (collection, token_id))?;
+    NAME_EXPIRIES.save(storage, (collection, token_id), &expires_at)?;
+    Ok(Some((years, expires_at, previous_expiry)))
+}
+
+/// Sends each beneficiary its share of the payment, or credits it, depending on the payout mode.
+fn pay_beneficiaries(
+    storage: &mut dyn Storage,
+    payment_params: &PaymentParams,
+    paid: &Coin,
+) -> StdResult<(Vec<BankMsg>, Vec<Attribute>)> {
+    let mut bank_msgs = Vec::<BankMsg>::new();
+    let mut payout_attributes = Vec::<Attribute>::new();
+    for (beneficiary, amount) in split_among_beneficiaries(payment_params, paid.amount) {
+        match payment_params.payout {
+            PayoutMode::Send => bank_msgs.push(BankMsg::Send {
+                to_address: beneficiary.to_string(),
+                amount: vec![Coin {
+                    denom: paid.denom.to_owned(),
+                    amount,
+                }],
+            }),
+            PayoutMode::Ledger => {
+                CLAIMABLE_BALANCES.update(
+                    storage,
+                    (&beneficiary, paid.denom.as_str()),
+                    |balance| -> StdResult<Uint128> {
+                        Ok(balance.unwrap_or_default().strict_add(amount))
+                    },
+                )?;
+                payout_attributes.push(Attribute::new("credit-beneficiary", beneficiary));
+                payout_attributes.push(Attribute::new("credit-amount", amount.to_string()));
+            }
+        }
+    }
+    Ok((bank_msgs, payout_attributes))
+}
+
+/// The prices outside of phases, when the collection has `token_count` tokens. Name length
+/// pricing needs the `token_id` to be minted.
+fn base_mint_prices(
+    payment_params: &PaymentParams,
+    env: &Env,
+    token_count: u64,
+    token_id: Option<&str>,
+) -> Result<Vec<Coin>, ContractError> {
+    Ok(match &payment_params.pricing {
+        PricingRule::Fixed => payment_params.mint_prices.to_owned(),
+        PricingRule::DutchAuction(dutch_auction) => vec![dutch_auction.price_at(env.block.time)],
+        PricingRule::BondingCurve(bonding_curve) => vec![bonding_curve.price_at(token_count)?],
+        PricingRule::NameLength(name_length_pricing) => {
+            let token_id = token_id.ok_or(ContractError::NameRequired)?;
+            vec![name_length_pricing.price_of(token_id).0]
+        }
+    })
+}
+
+/// Fails when the new payment params of the collection change its pricing to another auction with
+/// a rebate starting at the same time as one not yet settled, whose sales it would get mixed with.
+fn ensure_open_auction_kept(
+    storage: &dyn Storage,
+    collection: &Addr,
+    payment_params: &PaymentParams,
+) -> Result<(), ContractError> {
+    let PricingRule::DutchAuction(DutchAuction {
+        rebate: true,
+        start_time,
+        ..
+    }) = &payment_params.pricing
+    else {
+        return Ok(());
+    };
+    let open = AUCTION_SALES
+        .may_load(storage, (collection, start_time.seconds()))?
+        .is_some_and(|sales| !sales.settled);
+    if open && load_payment_params(storage, collection)?.pricing != payment_params.pricing {
+        return Err(ContractError::AuctionReconfigured {
+            collection: collection.to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Keeps the payment of a mint in an auction with a rebate, until the auction is settled.
+fn escrow_auction_purchase(
+    storage: &mut dyn Storage,
+    collection: &Addr,
+    auction: u64,
+    buyer: &Addr,
+    paid: &Coin,
+) -> Result<(), ContractError> {
+    let sales = AUCTION_SALES.may_load(storage, (collection, auction))?;
+    if sales.as_ref().is_some_and(|sales| sales.settled) {
+        return Err(ContractError::AuctionSettled {
+            collection: collection.to_string(),
+        });
+    }
+    AUCTION_SALES.save(
+        storage,
+        (collection, auction),
+        &AuctionSales {
+            last_price: paid.to_owned(),
+            minted: sales.map_or(0, |sales| sales.minted) + 1,
+            settled: false,
+        },
+    )?;
+    AUCTION_PURCHASES.update(
+        storage,
+        (collection, buyer, auction),
+        |purchase| -> StdResult<AuctionPurchase> {
+            let purchase = purchase.unwrap_or_default();
+            Ok(AuctionPurchase {
+                minted: purchase.minted + 1,
+                paid: purchase.paid.strict_add(paid.amount),
+            })
+        },
+    )?;
+    Ok(())
+}
+
+/// Returns the phase active at the current block, if the payment params have phases, after
+/// counting the mint against its cap and the minter's allowance.
+fn use_mint_phase(
+    storage: &mut dyn Storage,
+    env: &Env,
+    collection: &Addr,
+    minter: &Addr,
+    payment_params: &PaymentParams,
+    options: &PassThroughOptions,
+) -> Result<Option<MintPhase>, ContractError> {
+    if payment_params.phases.is_empty() {
+        return Ok(None);
+    }
+    let phase = payment_params
+        .phases
+        .iter()
+        .find(|phase| phase.is_active(&env.block))
+        .ok_or(ContractError::NoActivePhase)?;
+    let minted = PHASE_MINT_COUNTS
+        .may_load(storage, (collection, phase.name.as_str()))?
+        .unwrap_or_default();
+    if phase.cap.is_some_and(|cap| minted >= cap) {
+        return Err(ContractError::PhaseCapReached {
+            phase: phase.name.to_owned(),
+        });
+    }
+    let used = PHASE_WALLET_MINTS
+        .may_load(storage, (collection, phase.name.as_str(), minter))?
+        .unwrap_or_default();
+    if let Some(max_per_wallet) = phase.max_per_wallet {
+        if used >= max_per_wallet {
+            return Err(ContractError::PhaseWalletLimitReached {
+                phase: phase.name.to_owned(),
+                limit: max_per_wallet,
+            });
+        }
+    }
+    if let Some(allowlist_root) = &phase.allowlist_root {
+        let allowlist =
+            options
+                .allowlist
+                .as_ref()
+                .ok_or_else(|| ContractError::AllowlistProofRequired {
+                    phase: phase.name.to_owned(),
+                })?;
+        if !verify_allowlist_proof(allowlist_root, minter, allowlist) {
+            return Err(ContractError::InvalidAllowlistProof);
+        }
+        if used >= allowlist.allocation {
+            return Err(ContractError::AllocationUsedUp {
+                phase: phase.name.to_owned(),
+                allocation: allowlist.allocation,
+            });
+        }
+    }
+    PHASE_WALLET_MINTS.save(
+        storage,
+        (collection, phase.name.as_str(), minter),
+        &(used + 1),
+    )?;
+    PHASE_MINT_COUNTS.save(storage, (collection, phase.name.as_str()), &(minted + 1))?;
+    Ok(Some(phase.to_owned()))
+}
+
+/// Counts the mint against the collection's limits, where `token_count` is the collection's own
+/// count, a cross-check of the supply.
+fn use_mint_limits(
+    storage: &mut dyn Storage,
+    collection: &Addr,
+    minter: &Addr,
+    token_count: u64,
+) -> Result<(), ContractError> {
+    let limits = COLLECTION_MINT_LIMITS
+        .may_load(storage, collection)?
+        .unwrap_or_default();
+    let collection_minted = COLLECTION_MINTS
+        .may_load(storage, collection)?
+        .unwrap_or_default();
+    if let Some(max_supply) = limits.max_supply {
+        if collection_minted.max(token_count) >= max_supply {
+            return Err(ContractError::MaxSupplyReached { max_supply });
+        }
+    }
+    let wallet_minted = WALLET_MINTS
+        .may_load(storage, (collection, minter))?
+        .unwrap_or_default();
+    if let Some(max_per_wallet) = limits.max_per_wallet {
+        if wallet_minted >= max_per_wallet {
+            return Err(ContractError::WalletLimitReached {
+                limit: max_per_wallet,
+            });
+        }
+    }
+    COLLECTION_MINTS.save(storage, collection, &(collection_minted + 1))?;
+    WALLET_MINTS.save(storage, (collection, minter), &(wallet_minted + 1))?;
+    Ok(())
+}
+
+/// Follows the proof from the leaf of the address and its allocation, up to the root.
+fn verify_allowlist_proof(root: &HexBinary, address: &Addr, allowlist: &AllowlistProof) -> bool {
+    let leaf: [u8; 32] =
+        Sha256::digest(format!("{}:{}", address, allowlist.allocation).as_bytes()).into();
+    let computed = allowlist.proof.iter().fold(leaf, |node, sibling| {
+        let (left, right) = if node.as_slice() <= sibling.as_slice() {
+            (node.as_slice(), sibling.as_slice())
+        } else {
+            (sibling.as_slice(), node.as_slice())
+        };
+        Sha256::new()
+            .chain_update(left)
+            .chain_update(right)
+            .finalize()
+            .into()
+    });
+    computed.as_slice() == root.as_slice()
+}
+
+/// Rounds each share down and gives the remainder to the first beneficiary. Omits empty shares.
+fn split_among_beneficiaries(
+    payment_params: &PaymentParams,
+    amount: Uint128,
+) -> Vec<(Addr, Uint128)> {
+    let mut shares = payment_params
+        .beneficiaries
+        .iter()
+        .map(|beneficiary| {
+            (
+                beneficiary.address.to_owned(),
+                amount.multiply_ratio(beneficiary.share_bps, TOTAL_SHARE_BPS),
+            )
+        })
+        .collect::<Vec<_>>();
+    let distributed = shares
+        .iter()
+        .fold(Uint128::zero(), |distributed, (_, share)| {
+            distributed.strict_add(*share)
+        });
+    if let Some((_, first_share)) = shares.first_mut() {
+        *first_share = first_share.strict_add(amount.strict_sub(distributed));
+    }
+    shares.retain(|(_, share)| !share.is_zero());
+    shares
+}
+
+fn load_payment_params(storage: &dyn Storage, collection: &Addr) -> StdResult<PaymentParams> {
+    match COLLECTION_PAYMENT_PARAMS.may_load(storage, collection)? {
+        Some(payment_params) => Ok(payment_params),
+        None => PAYMENT_PARAMS.load(storage),
+    }
+}
+
+fn split_fund_denom(denom: &String, funds: &[Coin]) -> (Uint128, Vec<Coin>) {
+    let (amount, others) = funds.iter().fold(
+        (Uint128::zero(), Vec::with_capacity(funds.len())),
+        |(aggregated, mut others), fund| {
+            if &fund.denom == denom {
+                (aggregated.strict_add(fund.amount), others)
+            } else {
+                others.push(fund.clone());
+                (aggregated, others)
+            }
+        },
+    );
+    (amount, others)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> ContractResult {
+    match ReplyCode::try_from(msg.id)? {
+        ReplyCode::PassThrough | ReplyCode::PendingPassThrough => {
+            reply_pass_through(deps, env, msg)
+        }
+        ReplyCode::CreateCollection => reply_create_collection(deps, env, msg),
+        ReplyCode::BatchItem => reply_batch_item(deps, env, msg),
+    }
+}
+
+fn reply_batch_item(deps: DepsMut, _env: Env, msg: Reply) -> ContractResult {
+    let offset = msg.id - ReplyCode::BatchItem as u64;
+    let batch_number = offset / MAX_BATCH_ITEMS as u64;
+    let index = (offset % MAX_BATCH_ITEMS as u64) as u32;
+    let mut batch = PENDING_BATCHES.load(deps.storage, batch_number)?;
+    let item = batch
+        .items
+        .get(index as usize)
+        .cloned()
+        .ok_or_else(|| StdError::generic_err(format!("unknown batch item {}", index)))?;
+    let mut response = Response::default();
+    let mut event = Event::new("my-collection-manager")
+        .add_attribute("batch-item", index.to_string())
+        .add_attribute("batch-item-collection", item.collection.to_owned());
+    match msg.result {
+        SubMsgResult::Ok(_) => {
+            event = event.add_attribute("batch-item-status", "succeeded");
+            if let Some(mint) = &item.mint {
+                let (payout_msgs, payout_attributes) =
+                    settle_mint_payment(deps.storage, &item.collection, &batch.minter, mint)?;
+                response = response.add_messages(payout_msgs);
+                event = event.add_attributes(payout_attributes);
+            }
+            batch.succeeded.push(index);
+        }
+        SubMsgResult::Err(error) => {
+            event = event
+                .add_attribute("batch-item-status", "failed")
+                .add_attribute("batch-item-error", error.to_owned());
+            if let Some(mint) = &item.mint {
+                undo_mint(deps.storage, &item.collection, &batch.minter, mint)?;
+                if let Some(paid) = &mint.payment {
+                    event = event
+                        .add_attribute("batch-item-refund-denom", paid.denom.to_owned())
+                        .add_attribute("batch-item-refund-amount", paid.amount.to_string());
+                    response = response.add_message(BankMsg::Send {
+                        to_address: batch.minter.to_string(),
+                        amount: vec![paid.to_owned()],
+                    });
+                }
+            }
+            batch.failed.push(BatchItemFailure { index, error });
+        }
+    }
+    if batch.succeeded.len() + batch.failed.len() < batch.items.len() {
+        PENDING_BATCHES.save(deps.storage, batch_number, &batch)?;
+    } else {
+        PENDING_BATCHES.remove(deps.storage, batch_number);
+        response = response.set_data(to_json_binary(&BatchResponse {
+            succeeded: batch.succeeded,
+            failed: batch.failed,
+        })?);
+    }
+    Ok(response.add_event(event))
+}
+
+fn reply_create_collection(deps: DepsMut, env: Env, msg: Reply) -> ContractResult {
+    let instantiate_response = parse_reply_instantiate_data(msg)?;
+    let collection = deps
+        .api
+        .addr_validate(&instantiate_response.contract_address)?;
+    let label = PENDING_COLLECTION_LABEL.load(deps.storage)?;
+    PENDING_COLLECTION_LABEL.remove(deps.storage);
+    register_collection(deps.storage, &env, &collection, label)?;
+    let event = Event::new("my-collection-manager").add_attribute("create-collection", collection);
+    Ok(Response::default().add_event(event))
+}
+
+fn reply_pass_through(deps: DepsMut, _env: Env, msg: Reply) -> ContractResult {
+    // Only pass-throughs catching the errors of the collection, or returning its data, are
+    // pending.
+    let pending = PENDING_PASS_THROUGHS.may_load(deps.storage, msg.id)?;
+    PENDING_PASS_THROUGHS.remove(deps.storage, msg.id);
+    let resp = match (msg.result, pending.as_ref()) {
+        (SubMsgResult::Ok(resp), _) => resp,
+        (SubMsgResult::Err(error), Some(pending)) => {
+            return reply_caught_pass_through(deps, pending, error)
+        }
+        (SubMsgResult::Err(error), None) => return Err(StdError::generic_err(error).into()),
+    };
+    let mut response = Response::default();
+    let mut attributes = Vec::<Attribute>::new();
+    let data = resp.data.and_then(unwrap_execute_data);
+    let mut token_count_after = None;
+    if let Some(data) = &data {
+        match from_json::<NameServiceExecuteMsgResponse>(data) {
+            Ok(value) => {
+                attributes.push(Attribute::new(
+                    "token-count-after",
+                    value.num_tokens.to_string(),
+                ));
+                token_count_after = Some(value.num_tokens);
+            }
+            Err(error) => attributes.push(Attribute::new(
+                "token-count-decode-error",
+                error.to_string(),
+            )),
+        }
+    }
+    if let Some(pending) = pending {
+        if let Some(mint) = &pending.mint {
+            let (payout_msgs, payout_attributes) =
+                settle_mint_payment(deps.storage, &pending.collection, &pending.minter, mint)?;
+            response = response.add_messages(payout_msgs);
+            attributes.extend(payout_attributes);
+        }
+        match pending.return_data {
+            None => {}
+            Some(DataFormat::Raw) => {
+                if let Some(data) = data {
+                    response = response.set_data(data);
+                }
+            }
+            Some(DataFormat::Envelope) => {
+                response = response.set_data(to_json_binary(&PassThroughData {
+                    collection: pending.collection,
+                    token_count_before: pending.token_count_before,
+                    token_count_after,
+                    data,
+                })?);
+            }
+        }
+    }
+    if attributes.is_empty() {
+        return Ok(response);
+    }
+    Ok(response.add_event(Event::new("my-collection-manager").add_attributes(attributes)))
+}
+
+/// Returns the data of the collection, whether wrapped in the `MsgExecuteContractResponse` of
+/// the chain or not.
+fn unwrap_execute_data(data: Binary) -> Option<Binary> {
+    match parse_execute_response_data(&data) {
+        Ok(execute_response) => execute_response.data,
+        Err(_) => Some(data),
+    }
+}
+
+/// Refunds the mint payment of a pass-through the collection rejected, and explains the
+/// rejection in the response data.
+fn reply_caught_pass_through(
+    deps: DepsMut,
+    pending: &PendingPassThrough,
+    error: String,
+) -> ContractResult {
+    let mut response = Response::default();
+    let mut event = Event::new("my-collection-manager")
+        .add_attribute(
+            "pass-through-failed-collection",
+            pending.collection.to_owned(),
+        )
+        .add_attribute("pass-through-failed-kind", pending.kind.as_str())
+        .add_attribute("pass-through-error", error.to_owned());
+    if let Some(mint) = &pending.mint {
+        undo_mint(deps.storage, &pending.collection, &pending.minter, mint)?;
+        if let Some(paid) = &mint.payment {
+            event = event
+                .add_attribute("pass-through-refund-denom", paid.denom.to_owned())
+                .add_attribute("pass-through-refund-amount", paid.amount.to_string());
+            response = response.add_message(BankMsg::Send {
+                to_address: pending.minter.to_string(),
+                amount: vec![paid.to_owned()],
+            });
+        }
+    }
+    let failure = PassThroughFailure {
+        collection: pending.collection.to_owned(),
+        kind: pending.kind,
+        error,
+    };
+    Ok(response
+        .set_data(to_json_binary(&failure)?)
+        .add_event(event))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<QueryResponse, ContractError> {
+    match msg {
+        QueryMsg::GetPaymentParams {} => Ok(to_json_binary(&GetPaymentParamsResponse {
+            payment_params: PAYMENT_PARAMS.load(deps.storage)?,
+        })?),
+        QueryMsg::GetCollectionPaymentParams { collection } => Ok(to_json_binary(
+            &query_collection_payment_params(deps, collection)?,
+        )?),
+        QueryMsg::ListCollectionPaymentParams { start_after, limit } => Ok(to_json_binary(
+            &query_list_collection_payment_params(deps, start_after, limit)?,
+        )?),
+        QueryMsg::ListCollections { start_after, limit } => Ok(to_json_binary(
+            &query_list_collections(deps, start_after, limit)?,
+        )?),
+        QueryMsg::PredictCollectionAddress { code_id, salt } => Ok(to_json_binary(
+            &query_predict_collection_address(deps, env, code_id, salt)?,
+        )?),
+        QueryMsg::Ownership {} => Ok(to_json_binary(&get_ownership(deps.storage)?)?),
+        QueryMsg::HasRole { role, address } => {
+            let address = deps.api.addr_validate(&address)?;
+            Ok(to_json_binary(&HasRoleResponse {
+                has_role: ROLE_MEMBERS.has(deps.storage, (role.as_str(), &address)),
+            })?)
+        }
+        QueryMsg::ListRoleMembers {
+            role,
+            start_after,
+            limit,
+        } => Ok(to_json_binary(&query_list_role_members(
+            deps,
+            role,
+            start_after,
+            limit,
+        )?)?),
+        QueryMsg::GetPassThroughPolicies {} => {
+            Ok(to_json_binary(&query_pass_through_policies(deps)?)?)
+        }
+        QueryMsg::PauseStatus { collection } => {
+            Ok(to_json_binary(&query_pause_status(deps, env, collection)?)?)
+        }
+        QueryMsg::CurrentPhase { collection } => Ok(to_json_binary(&query_current_phase(
+            deps, env, collection,
+        )?)?),
+        QueryMsg::GetMintLimits {
+            collection,
+            address,
+        } => Ok(to_json_binary(&query_mint_limits(
+            deps, collection, address,
+        )?)?),
+        QueryMsg::AllowlistEligibility {
+            collection,
+            address,
+            allowlist,
+        } => Ok(to_json_binary(&query_allowlist_eligibility(
+            deps, env, collection, address, allowlist,
+        )?)?),
+        QueryMsg::CurrentMintPrice { collection } => Ok(to_json_binary(
+            &query_current_mint_price(deps, env, collection)?,
+        )?),
+        QueryMsg::QuoteMint {
+            collection,
+            quantity,
+        } => Ok(to_json_binary(&query_quote_mint(
+            deps, env, collection, quantity,
+        )?)?),
+        QueryMsg::QuoteName { collection, name } => Ok(to_json_binary(&query_quote_name(
+            deps, env, collection, name,
+        )?)?),
+        QueryMsg::GetNameRules { collection } => {
+            let collection = deps.api.addr_validate(&collection)?;
+            Ok(to_json_binary(&GetNameRulesResponse {
+                rules: COLLECTION_NAME_RULES.may_load(deps.storage, &collection)?,
+            })?)
+        }
+        QueryMsg::NameExpiry {
+            collection,
+            token_id,
+        } => {
+            let collection = deps.api.addr_validate(&collection)?;
+            let expires_at =
+                NAME_EXPIRIES.may_load(deps.storage, (&collection, token_id.as_str()))?;
+            let grace_period = load_payment_params(deps.storage, &collection)?
+                .subscription
+                .map_or(0, |subscription| subscription.grace_period);
+            Ok(to_json_binary(&NameExpiryResponse {
+                expires_at,
+                grace_ends_at: expires_at.map(|expires_at| expires_at.plus_seconds(grace_period)),
+            })?)
+        }
+        QueryMsg::ClaimableBalance { beneficiary, denom } => {
+            let beneficiary = deps.api.addr_validate(&beneficiary)?;
+            let amount = CLAIMABLE_BALANCES
+                .may_load(deps.storage, (&beneficiary, denom.as_str()))?
+                .unwrap_or_default();
+            Ok(to_json_binary(&ClaimableBalanceResponse {
+                balance: Coin { denom, amount },
+            })?)
+        }
+    }
+}
+
+fn query_pass_through_policies(
+    deps: Deps,
+) -> Result<GetPassThroughPoliciesResponse, ContractError> {
+    let policies = CollectionMsgKind::ALL
+        .into_iter()
+        .map(|kind| {
+            load_pass_through_policy(deps.storage, kind)
+                .map(|policy| KindPassThroughPolicy { kind, policy })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(GetPassThroughPoliciesResponse { policies })
+}
+
+fn query_current_phase(
+    deps: Deps,
+    env: Env,
+    collection: String,
+) -> Result<CurrentPhaseResponse, ContractError> {
+    let collection = deps.api.addr_validate(&collection)?;
+    let payment_params = load_payment_params(deps.storage, &collection)?;
+    let phase = payment_params
+        .phases
+        .iter()
+        .find(|phase| phase.is_active(&env.block))
+        .cloned();
+    let minted = match &phase {
+        None => 0,
+        Some(phase) => PHASE_MINT_COUNTS
+            .may_load(deps.storage, (&collection, phase.name.as_str()))?
+            .unwrap_or_default(),
+    };
+    Ok(CurrentPhaseResponse {
+        has_phases: !payment_params.phases.is_empty(),
+        phase,
+        minted,
+    })
+}
+
+fn query_current_mint_price(
+    deps: Deps,
+    env: Env,
+    collection: String,
+) -> Result<CurrentMintPriceResponse, ContractError> {
+    let collection = deps.api.addr_validate(&collection)?;
+    let payment_params = load_payment_params(deps.storage, &collection)?;
+    let phase = payment_params
+        .phases
+        .iter()
+        .find(|phase| phase.is_active(&env.block));
+    Ok(match phase {
+        None => CurrentMintPriceResponse {
+            mint_prices: base_mint_prices(
+                &payment_params,
+                &env,
+                query_token_count(deps, &collection)?,
+                None,
+            )?,
+            phase: None,
+        },
+        Some(phase) => CurrentMintPriceResponse {
+            mint_prices: phase.mint_prices.to_owned(),
+            phase: Some(phase.name.to_owned()),
+        },
+    })
+}
+
+fn query_quote_mint(
+    deps: Deps,
+    env: Env,
+    collection: String,
+    quantity: u64,
+) -> Result<QuoteMintResponse, ContractError> {
+    let collection = deps.api.addr_validate(&collection)?;
+    let payment_params = load_payment_params(deps.storage, &collection)?;
+    let token_count = query_token_count(deps, &collection)?;
+    let phase = payment_params
+        .phases
+        .iter()
+        .find(|phase| phase.is_active(&env.block));
+    let mint_prices = match (phase, &payment_params.pricing) {
+        (Some(phase), _) => phase.mint_prices.to_owned(),
+        // Each mint of the batch raises the price of the next one.
+        (None, PricingRule::BondingCurve(bonding_curve)) => {
+            let total = (token_count..token_count.saturating_add(quantity)).try_fold(
+                Uint128::zero(),
+                |total, supply| -> StdResult<Uint128> {
+                    Ok(total.checked_add(bonding_curve.price_at(supply)?.amount)?)
+                },
+            )?;
+            return Ok(QuoteMintResponse {
+                totals: vec![Coin {
+                    denom: bonding_curve.denom.to_owned(),
+                    amount: total,
+                }],
+            });
+        }
+        (None, _) => base_mint_prices(&payment_params, &env, token_count, None)?,
+    };
+    Ok(QuoteMintResponse {
+        totals: multiply_prices(mint_prices, quantity)?,
+    })
+}
+
+fn query_quote_name(
+    deps: Deps,
+    env: Env,
+    collection: String,
+    name: String,
+) -> Result<QuoteNameResponse, ContractError> {
+    let collection = deps.api.addr_validate(&collection)?;
+    let payment_params = load_payment_params(deps.storage, &collection)?;
+    if let Some(phase) = payment_params
+        .phases
+        .iter()
+        .find(|phase| phase.is_active(&env.block))
+    {
+        return Ok(QuoteNameResponse {
+            mint_prices: phase.mint_prices.to_owned(),
+            premium: false,
+        });
+    }
+    if let PricingRule::NameLength(name_length_pricing) = &payment_params.pricing {
+        let (mint_price, premium) = name_length_pricing.price_of(&name);
+        return Ok(QuoteNameResponse {
+            mint_prices: vec![mint_price],
+            premium,
+        });
+    }
+    let token_count = query_token_count(deps, &collection)?;
+    Ok(QuoteNameResponse {
+        mint_prices: base_mint_prices(&payment_params, &env, token_count, Some(&name))?,
+        premium: false,
+    })
+}
+
+fn query_mint_limits(
+    deps: Deps,
+    collection: String,
+    address: Option<String>,
+) -> Result<GetMintLimitsResponse, ContractError> {
+    let collection = deps.api.addr_validate(&collection)?;
+    let minted_by_address = match address {
+        None => None,
+        Some(address) => {
+            let address = deps.api.addr_validate(&address)?;
+            Some(
+                WALLET_MINTS
+                    .may_load(deps.storage, (&collection, &address))?
+                    .unwrap_or_default(),
+            )
+        }
+    };
+    Ok(GetMintLimitsResponse {
+        limits: COLLECTION_MINT_LIMITS
+            .may_load(deps.storage, &collection)?
+            .unwrap_or_default(),
+        minted: COLLECTION_MINTS
+            .may_load(deps.storage, &collection)?
+            .unwrap_or_default(),
+        minted_by_address,
+    })
+}
+
+fn query_allowlist_eligibility(
+    deps: Deps,
+    env: Env,
+    collection: String,
+    address: String,
+    allowlist: Option<AllowlistProof>,
+) -> Result<AllowlistEligibilityResponse, ContractError> {
+    let collection = deps.api.addr_validate(&collection)?;
+    let address = deps.api.addr_validate(&address)?;
+    let payment_params = load_payment_params(deps.storage, &collection)?;
+    if payment_params.phases.is_empty() {
+        return Ok(AllowlistEligibilityResponse {
+            eligible: true,
+            phase: None,
+            remaining_allocation: None,
+        });
+    }
+    let Some(phase) = payment_params
+        .phases
+        .into_iter()
+        .find(|phase| phase.is_active(&env.block))
+    else {
+        return Ok(AllowlistEligibilityResponse {
+            eligible: false,
+            phase: None,
+            remaining_allocation: None,
+        });
+    };
+    let remaining_allocation = match (&phase.allowlist_root, allowlist) {
+        (None, _) => None,
+        (Some(allowlist_root), Some(allowlist))
+            if verify_allowlist_proof(allowlist_root, &address, &allowlist) =>
+        {
+            let used = PHASE_WALLET_MINTS
+                .may_load(deps.storage, (&collection, phase.name.as_str(), &address))?
+                .unwrap_or_default();
+            Some(allowlist.allocation.saturating_sub(used))
+        }
+        (Some(_), _) => Some(0),
+    };
+    Ok(AllowlistEligibilityResponse {
+        eligible: remaining_allocation != Some(0),
+        phase: Some(phase.name),
+        remaining_allocation,
+    })
+}
+
+fn query_pause_status(
+    deps: Deps,
+    env: Env,
+    collection: Option<String>,
+) -> Result<PauseStatusResponse, ContractError> {
+    let active = |pause: PauseInfo| pause.is_active(env.block.height).then_some(pause);
+    let global = GLOBAL_PAUSE.may_load(deps.storage)?.and_then(active);
+    let collection = match collection {
+        None => None,
+        Some(collection) => {
+            let collection = deps.api.addr_validate(&collection)?;
+            COLLECTION_PAUSES
+                .may_load(deps.storage, &collection)?
+                .and_then(active)
+        }
+    };
+    Ok(PauseStatusResponse {
+        paused: global.is_some() || collection.is_some(),
+        global,
+        collection,
+    })
+}
+
+fn query_collection_payment_params(
+    deps: Deps,
+    collection: String,
+) -> Result<GetCollectionPaymentParamsResponse, ContractError> {
+    let collection = deps.api.addr_validate(&collection)?;
+    let (payment_params, is_default) =
+        match COLLECTION_PAYMENT_PARAMS.may_load(deps.storage, &collection)? {
+            Some(payment_params) => (payment_params, false),
+            None => (PAYMENT_PARAMS.load(deps.storage)?, true),
+        };
+    Ok(GetCollectionPaymentParamsResponse {
+        collection,
+        payment_params,
+        is_default,
+    })
+}
+
+fn query_list_collection_payment_params(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> Result<ListCollectionPaymentParamsResponse, ContractError> {
+    let start_after = start_after
+        .map(|collection| deps.api.addr_validate(&collection))
+        .transpose()?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let collections = COLLECTION_PAYMENT_PARAMS
+        .range(
+            deps.storage,
+            start_after.as_ref().map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .map(|item| {
+            item.map(|(collection, payment_params)| CollectionPaymentParams {
+                collection,
+                payment_params,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(ListCollectionPaymentParamsResponse { collections })
+}
+
+fn query_list_collections(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> Result<ListCollectionsResponse, ContractError> {
+    let start_after = start_after
+        .map(|collection| deps.api.addr_validate(&collection))
+        .transpose()?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let collections = COLLECTIONS
+        .range(
+            deps.storage,
+            start_after.as_ref().map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .map(|item| item.map(|(collection, record)| ManagedCollection { collection, record }))
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(ListCollectionsResponse { collections })
+}
+
+fn query_list_role_members(
+    deps: Deps,
+    role: Role,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> Result<ListRoleMembersResponse, ContractError> {
+    let start_after = start_after
+        .map(|member| deps.api.addr_validate(&member))
+        .transpose()?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let members = ROLE_MEMBERS
+        .prefix(role.as_str())
+        .keys(
+            deps.storage,
+            start_after.as_ref().map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(ListRoleMembersResponse { members })
+}
+
+fn query_predict_collection_address(
+    deps: Deps,
+    env: Env,
+    code_id: u64,
+    salt: Binary,
+) -> Result<PredictCollectionAddressResponse, ContractError> {
+    let code_info = deps.querier.query_wasm_code_info(code_id)?;
+    let creator = deps.api.addr_canonicalize(env.contract.address.as_str())?;
+    let address = instantiate2_address(code_info.checksum.as_slice(), &creator, &salt)?;
+    Ok(PredictCollectionAddressResponse {
+        address: deps.api.addr_humanize(&address)?,
+    })
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn sudo(deps: DepsMut, env: Env, msg: SudoMsg) -> ContractResult {
+    match msg {
+        SudoMsg::UpdatePaymentParams(payment_params) => {
+            sudo_update_payment_params(deps, payment_params)
+        }
+        SudoMsg::UpdateCollectionPaymentParams {
+            collection,
+            payment_params,
+        } => sudo_update_collection_payment_params(deps, collection, payment_params),
+        SudoMsg::ClearCollectionPaymentParams { collection } => {
+            sudo_clear_collection_payment_params(deps, collection)
+        }
+        SudoMsg::SetMintLimits { collection, limits } => {
+            sudo_set_mint_limits(deps, collection, limits)
+        }
+        SudoMsg::SetNameRules { collection, rules } => sudo_set_name_rules(deps, collection, rules),
+        SudoMsg::ClearNameRules { collection } => sudo_clear_name_rules(deps, collection),
+        SudoMsg::RegisterCollection { collection, label } => {
+            sudo_register_collection(deps, env, collection, label)
+        }
+        SudoMsg::UnregisterCollection { collection } => {
+            sudo_unregister_collection(deps, collection)
+        }
+        SudoMsg::Pause {
+            collection,
+            until_height,
+        } => sudo_pause(deps, env, collection, until_height),
+        SudoMsg::Unpause { collection } => sudo_unpause(deps, collection),
+        SudoMsg::SettleAuction { collection } => sudo_settle_auction(deps, collection),
+    }
+}
+
+fn sudo_pause(
+    deps: DepsMut,
+    env: Env,
+    collection: Option<String>,
+    until_height: Option<u64>,
+) -> ContractResult {
+    if let Some(until_height) = until_height {
+        if until_height <= env.block.height {
+            return Err(ContractError::InvalidPauseHeight {
+                current_height: env.block.height,
+            });
+        }
+    }
+    let pause = PauseInfo { until_height };
+    let pause_event = match collection {
+        None => {
+            GLOBAL_PAUSE.save(deps.storage, &pause)?;
+            Event::new("my-collection-manager").add_attribute("pause", "global")
+        }
+        Some(collection) => {
+            let collection = deps.api.addr_validate(&collection)?;
+            COLLECTION_PAUSES.save(deps.storage, &collection, &pause)?;
+            Event::new("my-collection-manager").add_attribute("pause", collection)
+        }
+    };
+    let pause_event = match until_height {
+        None => pause_event.add_attribute("pause-until-height", "none"),
+        Some(until_height) => {
+            pause_event.add_attribute("pause-until-height", until_height.to_string())
+        }
+    };
+    Ok(Response::default().add_event(pause_event))
+}
+
+fn sudo_unpause(deps: DepsMut, collection: Option<String>) -> ContractResult {
+    let unpause_event = match collection {
+        None => {
+            GLOBAL_PAUSE.remove(deps.storage);
+            Event::new("my-collection-manager").add_attribute("unpause", "global")
+        }
+        Some(collection) => {
+            let collection = deps.api.addr_validate(&collection)?;
+            COLLECTION_PAUSES.remove(deps.storage, &collection);
+            Event::new("my-collection-manager").add_attribute("unpause", collection)
+        }
+    };
+    Ok(Response::default().add_event(unpause_event))
+}
+
+fn sudo_update_payment_params(deps: DepsMut, payment_params: PaymentParams) -> ContractResult {
+    payment_params.validate()?;
+    let auction_collections = AUCTION_SALES
+        .keys(deps.storage, None, None, Order::Ascending)
+        .map(|key| key.map(|(collection, _)| collection))
+        .collect::<StdResult<Vec<_>>>()?;
+    for collection in auction_collections {
+        if !COLLECTION_PAYMENT_PARAMS.has(deps.storage, &collection) {
+            ensure_open_auction_kept(deps.storage, &collection, &payment_params)?;
+        }
+    }
+    PAYMENT_PARAMS.save(deps.storage, &payment_params)?;
+    let sudo_event = Event::new("my-collection-manager");
+    let sudo_event = append_payment_params_attributes(sudo_event, payment_params);
+    Ok(Response::default().add_event(sudo_event))
+}
+
+fn sudo_update_collection_payment_params(
+    deps: DepsMut,
+    collection: String,
+    payment_params: PaymentParams,
+) -> ContractResult {
+    let collection = deps.api.addr_validate(&collection)?;
+    payment_params.validate()?;
+    ensure_open_auction_kept(deps.storage, &collection, &payment_params)?;
+    COLLECTION_PAYMENT_PARAMS.save(deps.storage, &collection, &payment_params)?;
+    let sudo_event = Event::new("my-collection-manager")
+        .add_attribute("update-payment-params-collection", collection);
+    let sudo_event = append_payment_params_attributes(sudo_event, payment_params);
+    Ok(Response::default().add_event(sudo_event))
+}
+
+fn sudo_clear_collection_payment_params(deps: DepsMut, collection: String) -> ContractResult {
+    let collection = deps.api.addr_validate(&collection)?;
+    ensure_open_auction_kept(
+        deps.storage,
+        &collection,
+        &PAYMENT_PARAMS.load(deps.storage)?,
+    )?;
+    COLLECTION_PAYMENT_PARAMS.remove(deps.storage, &collection);
+    let sudo_event = Event::new("my-collection-manager")
+        .add_attribute("clear-payment-params-collection", collection);
+    Ok(Response::default().add_event(sudo_event))
+}
+
+fn sudo_set_mint_limits(deps: DepsMut, collection: String, limits: MintLimits) -> ContractResult {
+    let collection = deps.api.addr_validate(&collection)?;
+    COLLECTION_MINT_LIMITS.save(deps.storage, &collection, &limits)?;
+    let sudo_event = Event::new("my-collection-manager")
+        .add_attribute("update-mint-limits-collection", collection)
+        .add_attribute(
+            "update-mint-limits-max-per-wallet",
+            limits
+                .max_per_wallet
+                .map_or("none".to_owned(), |limit| limit.to_string()),
+        )
+        .add_attribute(
+            "update-mint-limits-max-supply",
+            limits
+                .max_supply
+                .map_or("none".to_owned(), |limit| limit.to_string()),
+        );
+    Ok(Response::default().add_event(sudo_event))
+}
+
+fn sudo_set_name_rules(deps: DepsMut, collection: String, rules: NameRules) -> ContractResult {
+    rules.validate()?;
+    let collection = deps.api.addr_validate(&collection)?;
+    COLLECTION_NAME_RULES.save(deps.storage, &collection, &rules)?;
+    let sudo_event = Event::new("my-collection-manager")
+        .add_attribute("update-name-rules-collection", collection)
+        .add_attribute(
+            "update-name-rules-charset",
+            match rules.charset {
+                Charset::AsciiLowercase => "ascii-lowercase",
+                Charset::Unicode => "unicode",
+            },
+        )
+        .add_attribute("update-name-rules-min-length", rules.min_length.to_string())
+        .add_attribute("update-name-rules-max-length", rules.max_length.to_string())
+        .add_attributes(
+            rules
+                .reserved_names
+                .into_iter()
+                .map(|name| Attribute::new("update-name-rules-reserved-name", name)),
+        );
+    Ok(Response::default().add_event(sudo_event))
+}
+
+fn sudo_clear_name_rules(deps: DepsMut, collection: String) -> ContractResult {
+    let collection = deps.api.addr_validate(&collection)?;
+    COLLECTION_NAME_RULES.remove(deps.storage, &collection);
+    let sudo_event = Event::new("my-collection-manager")
+        .add_attribute("clear-name-rules-collection", collection);
+    Ok(Response::default().add_event(sudo_event))
+}
+
+fn sudo_settle_auction(deps: DepsMut, collection: String) -> ContractResult {
+    let collection = deps.api.addr_validate(&collection)?;
+    let auctions = AUCTION_SALES
+        .prefix(&collection)
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    if auctions.is_empty() {
+        return Err(ContractError::NoAuctionSales {
+            collection: collection.to_string(),
+        });
+    }
+    // Settles the oldest open auction, in case the pricing moved on to the next one first.
+    let (auction, mut sales) = auctions
+        .into_iter()
+        .find(|(_, sales)| !sales.settled)
+        .ok_or_else(|| ContractError::AuctionSettled {
+            collection: collection.to_string(),
+        })?;
+    sales.settled = true;
+    AUCTION_SALES.save(deps.storage, (&collection, auction), &sales)?;
+    let proceeds = Coin {
+        denom: sales.last_price.denom.to_owned(),
+        amount: sales
+            .last_price
+            .amount
+            .checked_mul(Uint128::from(sales.minted))
+            .map_err(StdError::from)?,
+    };
+    let payment_params = load_payment_params(deps.storage, &collection)?;
+    let (bank_msgs, payout_attributes) =
+        pay_beneficiaries(deps.storage, &payment_params, &proceeds)?;
+    let settle_event = Event::new("my-collection-manager")
+        .add_attribute("settle-auction-collection", collection)
+        .add_attribute("settle-auction-start", auction.to_string())
+        .add_attribute("settle-auction-denom", proceeds.denom)
+        .add_attribute(
+            "settle-auction-clearing-price",
+            sales.last_price.amount.to_string(),
+        )
+        .add_attribute("settle-auction-proceeds", proceeds.amount.to_string())
+        .add_attributes(payout_attributes);
+    Ok(Response::default()
+        .add_messages(bank_msgs)
+        .add_event(settle_event))
+}
+
+fn sudo_register_collection(
+    deps: DepsMut,
+    env: Env,
+    collection: String,
+    label: String,
+) -> ContractResult {
+    let collection = deps.api.addr_validate(&collection)?;
+    register_collection(deps.storage, &env, &collection, label)?;
+    let sudo_event =
+        Event::new("my-collection-manager").add_attribute("register-collection", collection);
+    Ok(Response::default().add_event(sudo_event))
+}
+
+fn register_collection(
+    storage: &mut dyn Storage,
+    env: &Env,
+    collection: &Addr,
+    label: String,
+) -> Result<(), ContractError> {
+    if COLLECTIONS.has(storage, collection) {
+        return Err(ContractError::CollectionAlreadyRegistered {
+            collection: collection.to_string(),
+        });
+    }
+    COLLECTIONS.save(
+        storage,
+        collection,
+        &CollectionRecord {
+            label,
+            registered_at_height: env.block.height,
+        },
+    )?;
+    Ok(())
+}
+
+fn sudo_unregister_collection(deps: DepsMut, collection: String) -> ContractResult {
+    let collection = deps.api.addr_validate(&collection)?;
+    if !COLLECTIONS.has(deps.storage, &collection) {
+        return Err(ContractError::CollectionNotRegistered {
+            collection: collection.to_string(),
+        });
+    }
+    COLLECTIONS.remove(deps.storage, &collection);
+    let sudo_event =
+        Event::new("my-collection-manager").add_attribute("unregister-collection", collection);
+    Ok(Response::default().add_event(sudo_event))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> ContractResult {
+    if let Ok(ContractVersion {
+        contract: _,
+        version,
+    }) = get_contract_version(deps.storage)
+    {
+        return Err(ContractError::Version(VersionError::WrongVersion {
+            expected: "0.0.0".to_owned(),
+            found: version,
+        }));
+    }
+    msg.payment_params.validate()?;
+    PAYMENT_PARAMS.save(deps.storage, &msg.payment_params)?;
+    let ownership = initialize_owner(deps.storage, deps.api, msg.owner.as_deref())?;
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    let migrate_event = Event::new("my-collection-manager")
+        .add_attribute("update-contract-version", CONTRACT_VERSION);
+    let migrate_event = append_payment_params_attributes(migrate_event, msg.payment_params);
+    let migrate_event = append_owner_attribute(migrate_event, ownership.owner);
+    Ok(Response::default().add_event(migrate_event))
+}
+
+fn append_owner_attribute(my_event: Event, owner: Option<Addr>) -> Event {
+    match owner {
+        None => my_event.add_attribute("update-owner", "none"),
+        Some(owner) => my_event.add_attribute("update-owner", owner),
+    }
+}
+
+fn append_payment_params_attributes(my_event: Event, payment_params: PaymentParams) -> Event {
+    let my_event =
+        payment_params
+            .beneficiaries
+            .into_iter()
+            .fold(my_event, |my_event, beneficiary| {
+                my_event
+                    .add_attribute("update-payment-params-beneficiary", beneficiary.address)
+                    .add_attribute(
+                        "update-payment-params-beneficiary-share-bps",
+                        beneficiary.share_bps.to_string(),
+                    )
+            });
+    let my_event = match payment_params.cw20_mint_price {
+        None => my_event,
+        Some(cw20_mint_price) => my_event
+            .add_attribute(
+                "update-payment-params-cw20-mint-price-address",
+                cw20_mint_price.address,
+            )
+            .add_attribute(
+                "update-payment-params-cw20-mint-price-amount",
+                cw20_mint_price.amount.to_string(),
+            ),
+    };
+    let my_event = payment_params
+        .phases
+        .into_iter()
+        .fold(my_event, |my_event, phase| {
+            my_event.add_attribute("update-payment-params-phase", phase.name)
+        });
+    let my_event = match payment_params.pricing {
+        PricingRule::Fixed => my_event,
+        PricingRule::DutchAuction(dutch_auction) => my_event
+            .add_attribute(
+                "update-payment-params-dutch-auction-start-price",
+                dutch_auction.start_price.to_string(),
+            )
+            .add_attribute(
+                "update-payment-params-dutch-auction-floor-amount",
+                dutch_auction.floor_amount.to_string(),
+            )
+            .add_attribute(
+                "update-payment-params-dutch-auction-rebate",
+                dutch_auction.rebate.to_string(),
+            ),
+        PricingRule::BondingCurve(bonding_curve) => my_event
+            .add_attribute(
+                "update-payment-params-bonding-curve-denom",
+                bonding_curve.denom,
+            )
+            .add_attribute(
+                "update-payment-params-bonding-curve",
+                match bonding_curve.curve {
+                    Curve::Linear { .. } => "linear",
+                    Curve::Exponential { .. } => "exponential",
+                    Curve::Piecewise { .. } => "piecewise",
+                },
+            ),
+        PricingRule::NameLength(name_length_pricing) => my_event.add_attribute(
+            "update-payment-params-name-length-denom",
+            name_length_pricing.denom,
+        ),
+    };
+    let my_event = match payment_params.subscription {
+        None => my_event,
+        Some(subscription) => my_event
+            .add_attribute(
+                "update-payment-params-subscription-max-years",
+                subscription.max_years.to_string(),
+            )
+            .add_attribute(
+                "update-payment-params-subscription-grace-period",
+                subscription.grace_period.to_string(),
+            ),
+    };
+    if payment_params.mint_prices.is_empty() {
+        return my_event.add_attribute("update-payment-params-mint-price", "none");
+    }
+    payment_params
+        .mint_prices
+        .into_iter()
+        .fold(my_event, |my_event, mint_price| {
+            my_event
+                .add_attribute("update-payment-params-mint-price-denom", mint_price.denom)
+                .add_attribute(
+                    "update-payment-params-mint-price-amount",
+                    mint_price.amount.to_string(),
+                )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        contract::ReplyCode,
+        error::ContractError,
+        msg::{
+            AllowlistProof, BatchItemFailure, BatchMode, BatchResponse, Beneficiary, BondingCurve,
+            Charset, CollectionExecuteMsg, CollectionInstantiateMsg, CollectionMsgKind,
+            CollectionQueryMsg, CollectionRecord, CurrentMintPriceResponse, Curve, CurvePiece,
+            DataFormat, DutchAuction, ExecuteMsg, InstantiateMsg, LengthTier, MigrateMsg,
+            MintLimits, MintPhase, NameLengthPricing, NameRules, NameServiceExecuteMsgResponse,
+            PassThroughData, PassThroughFailure, PassThroughItem, PassThroughOptions,
+            PassThroughPolicy, PaymentParams, PayoutMode, PremiumName, PricingRule, QueryMsg,
+            QuoteMintResponse, QuoteNameResponse, Role, Subscription, SudoMsg, MAX_BATCH_ITEMS,
+            SECONDS_PER_YEAR,
+        },
+        state::{
+            COLLECTIONS, COLLECTION_PAYMENT_PARAMS, PAYMENT_PARAMS, PENDING_BATCHES,
+            PENDING_COLLECTION_LABEL, PENDING_PASS_THROUGHS, ROLE_MEMBERS, WALLET_MINTS,
+        },
+    };
+    use cosmwasm_std::{
+        from_json,
+        testing::{self, MockApi, MockQuerier, MockStorage},
+        to_json_binary, Addr, BankMsg, Binary, Coin, ContractResult, CosmosMsg, Empty, Event,
+        HexBinary, Order, OwnedDeps, Querier, QuerierResult, QueryRequest, Reply, ReplyOn,
+        Response, StdError, StdResult, Storage, SubMsg, SubMsgResponse, SubMsgResult, SystemError,
+        SystemResult, Uint128, WasmMsg, WasmQuery,
+    };
+    use cw2::{assert_contract_version, ContractVersion};
+    use cw20::Cw20CoinVerified;
+    use cw721::msg::{NumTokensResponse, OwnerOfResponse};
+    use cw_ownable::OwnershipError;
+    use cw_utils::{Expiration, Scheduled};
+    use sha2::{Digest, Sha256};
+    use std::marker::PhantomData;
+
+    /// Pays everything to one beneficiary at the given prices, with every other option left off.
+    fn payment_params(beneficiary: Addr, mint_prices: Vec<Coin>) -> PaymentParams {
+        PaymentParams {
+            beneficiaries: vec![Beneficiary {
+                address: beneficiary,
+                share_bps: 10_000,
+            }],
+            mint_prices,
+            payout: PayoutMode::Send,
+            cw20_mint_price: None,
+            phases: vec![],
+            pricing: PricingRule::Fixed,
+            subscription: None,
+        }
+    }
+
+    pub fn mock_deps(
+        response: NumTokensResponse,
+    ) -> OwnedDeps<MockStorage, MockApi, NumTokensMockQuerier, Empty> {
+        OwnedDeps {
+            storage: MockStorage::default(),
+            api: MockApi::default(),
+            querier: NumTokensMockQuerier::new(MockQuerier::new(&[]), response),
+            custom_query_type: PhantomData,
+        }
+    }
+
+    pub struct NumTokensMockQuerier {
+        base: MockQuerier,
+        response: NumTokensResponse,
+    }
+
+    impl Querier for NumTokensMockQuerier {
+        fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+            let request: QueryRequest<Empty> = match from_json(bin_request) {
+                Ok(v) => v,
+                Err(e) => {
+                    return SystemResult::Err(SystemError::InvalidRequest {
+                        error: format!("Parsing query request: {}", e),
+                        request: bin_request.into(),
+                    })
+                }
+            };
+
+            self.handle_query(&request)
+        }
+    }
+
+    impl NumTokensMockQuerier {
+        pub fn handle_query(&self, request: &QueryRequest<Empty>) -> QuerierResult {
+            match &request {
+                QueryRequest::Wasm(WasmQuery::Smart {
+                    contract_addr: _,
+                    msg,
+                }) => {
+                    let response = match from_json(msg).expect("Failed to parse collection query") {
+                        CollectionQueryMsg::NumTokens {} => to_json_binary(&self.response)
+                            .expect("Failed to serialize num tokens response"),
+                        CollectionQueryMsg::OwnerOf { .. } => to_json_binary(&OwnerOfResponse {
+                            owner: "owner".to_owned(),
+                            approvals: vec![],
+                        })
+                        .expect("Failed to serialize owner of response"),
+                        _ => panic!("Query is not num tokens nor owner of"),
+                    };
+                    SystemResult::Ok(ContractResult::Ok(response))
+                }
+                _ => self.base.handle_query(request),
+            }
+        }
+
+        pub fn new(base: MockQuerier<Empty>, response: NumTokensResponse) -> Self {
+            NumTokensMockQuerier { base, response }
+        }
+    }
+
+    #[test]
+    fn test_instantiate() {
+        // Arrange
+        let mut mocked_deps_mut = mock_deps(NumTokensResponse { count: 3 });
+        let mocked_env = testing::mock_env();
+        let deployer = Addr::unchecked("deployer");
+        let mocked_msg_info = testing::mock_info(deployer.as_ref(), &[]);
+        let payment_params = payment_params(deployer.to_owned(), vec![]);
+        let instantiate_msg = InstantiateMsg {
+            payment_params: payment_params.to_owned(),
+            owner: None,
+        };
+
+        // Act
+        let result = super::instantiate(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            mocked_msg_info,
+            instantiate_msg.to_owned(),
+        );
+
+        // Assert
+        assert!(result.is_ok(), "Failed to instantiate manager");
+        let received_response = result.unwrap();
+        let expected_response = Response::default().add_event(
+            Event::new("my-collection-manager")
+                .add_attribute("update-contract-version", "0.1.0")
+                .add_attribute("update-payment-params-beneficiary", deployer.to_owned())
+                .add_attribute("update-payment-params-beneficiary-share-bps", "10000")
+                .add_attribute("update-payment-params-mint-price", "none")
+                .add_attribute("update-owner", deployer),
+        );
+        assert_eq!(received_response, expected_response);
+        let saved_payment_params = PAYMENT_PARAMS
+            .load(&mocked_deps_mut.storage)
+            .expect("Failed to load payment params");
+        assert_eq!(saved_payment_params, payment_params);
+        assert_contract_version(&mocked_deps_mut.storage, "my-collection-manager", "0.1.0")
+            .expect("Failed to assert contract version");
+        let contract_info = cw2::CONTRACT
+            .load(&mocked_deps_mut.storage)
+            .expect("Failed to load contract info");
+        assert_eq!(
+            contract_info,
+            ContractVersion {
+                contract: "my-collection-manager".to_owned(),
+                version: "0.1.0".to_owned(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_pass_through() {
+        // Arrange
+        let mut mocked_deps_mut = mock_deps(NumTokensResponse { count: 3 });
+        let mocked_env = testing::mock_env();
+        let deployer = Addr::unchecked("deployer");
+        let mocked_msg_info = testing::mock_info(&deployer.to_string(), &[]);
+        let instantiate_msg = InstantiateMsg {
+            payment_params: payment_params(deployer.to_owned(), vec![]),
+            owner: None,
+        };
+        let _ = super::instantiate(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            mocked_msg_info,
+            instantiate_msg,
+        )
+        .expect("Failed to instantiate manager");
+        let _ = super::sudo(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            SudoMsg::RegisterCollection {
+                collection: "collection".to_owned(),
+                label: "my names".to_owned(),
+            },
+        )
+        .expect("Failed to register collection");
+        let executer = Addr::unchecked("executer");
+        let fund_sent = Coin {
+            denom: "gold".to_owned(),
+            amount: Uint128::from(335u128),
+        };
+        let mocked_msg_info = testing::mock_info(&executer.to_string(), &[fund_sent.to_owned()]);
+        let name = "alice".to_owned();
+        let owner = Addr::unchecked("owner");
+        let inner_msg = CollectionExecuteMsg::Mint {
+            token_id: name.to_owned(),
+            owner: owner.to_string(),
+            token_uri: None,
+            extension: None,
+        };
+        let execute_msg = ExecuteMsg::PassThrough {
+            collection: "collection".to_owned(),
+            message: inner_msg.to_owned(),
+            options: None,
+        };
+
+        // Act
+        let contract_result = super::execute(
+            mocked_deps_mut.as_mut(),
+            mocked_env,
+            mocked_msg_info,
+            execute_msg,
+        );
+
+        // Assert
+        assert!(contract_result.is_ok(), "Failed to pass message through");
+        let received_response = contract_result.unwrap();
+        let expected_response = Response::default()
+            .add_message(BankMsg::Send {
+                to_address: executer.to_string(),
+                amount: vec![fund_sent],
+            })
+            .add_submessage(SubMsg {
+                id: ReplyCode::PassThrough as u64,
+                msg: CosmosMsg::<Empty>::Wasm(WasmMsg::Execute {
+                    contract_addr: "collection".to_owned(),
+                    msg: to_json_binary(&inner_msg).expect("Failed to serialize inner message"),
+                    funds: vec![],
+                }),
+                reply_on: ReplyOn::Success,
+                gas_limit: None,
+            })
+            .add_event(
+                Event::new("my-collection-manager").add_attribute("token-count-before", "3"),
+            );
+        assert_eq!(received_response, expected_response);
+    }
+
+    #[test]
+    fn test_paid_mint_pass_through() {
+        // Arrange
+        let mut mocked_deps_mut = mock_deps(NumTokensResponse { count: 3 });
+        let mocked_env = testing::mock_env();
+        let beneficiary = Addr::unchecked("beneficiary");
+        let deployer = Addr::unchecked("deployer");
+        let mocked_msg_info = testing::mock_info(&deployer.to_string(), &[]);
+        let minting_price = Coin {
+            amount: Uint128::from(55u16),
+            denom: "silver".to_owned(),
+        };
+        let instantiate_msg = InstantiateMsg {
+            payment_params: payment_params(beneficiary.to_owned(), vec![minting_price.to_owned()]),
+            owner: None,
+        };
+        let _ = super::instantiate(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            mocked_msg_info,
+            instantiate_msg,
+        )
+        .expect("Failed to instantiate manager");
+        let _ = super::sudo(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            SudoMsg::RegisterCollection {
+                collection: "collection".to_owned(),
+                label: "my names".to_owned(),
+            },
+        )
+        .expect("Failed to register collection");
+        let executer = Addr::unchecked("executer");
+        let extra_fund_sent = Coin {
+            denom: "gold".to_owned(),
+            amount: Uint128::from(335u128),
+        };
+        let fistful_silver = Coin {
+            amount: Uint128::from(30u16),
+            denom: "silver".to_owned(),
+        };
+        let mocked_msg_info = testing::mock_info(
+            &executer.to_string(),
+            &[
+                extra_fund_sent.to_owned(),
+                fistful_silver.to_owned(),
+                fistful_silver,
+            ],
+        );
+        let name = "alice".to_owned();
+        let owner = Addr::unchecked("owner");
+        let inner_msg = CollectionExecuteMsg::Mint {
+            token_id: name.to_owned(),
+            owner: owner.to_string(),
+            token_uri: None,
+            extension: None,
+        };
+        let execute_msg = ExecuteMsg::PassThrough {
+            collection: "collection".to_owned(),
+            message: inner_msg.to_owned(),
+            options: None,
+        };
+
+        // Act
+        let contract_result = super::execute(
+            mocked_deps_mut.as_mut(),
+            mocked_env,
+            mocked_msg_info.to_owned(),
+            execute_msg,
+        );
+
+        // Assert
+        assert!(contract_result.is_ok(), "Failed to pass message through");
+        let received_response = contract_result.unwrap();
+        let expected_denom_change = Coin {
+            amount: Uint128::from(5u16),
+            denom: "silver".to_owned(),
+        };
+        let expected_response = Response::default()
+            .add_message(BankMsg::Send {
+                to_address: beneficiary.to_string(),
+                amount: vec![minting_price],
+            })
+            .add_message(BankMsg::Send {
+                to_address: mocked_msg_info.sender.to_string(),
+                amount: vec![extra_fund_sent, expected_denom_change],
+            })
+            .add_submessage(SubMsg {
+                id: ReplyCode::PassThrough as u64,
+                msg: CosmosMsg::<Empty>::Wasm(WasmMsg::Execute {
+                    contract_addr: "collection".to_owned(),
+                    msg: to_json_binary(&inner_msg).expect("Failed to serialize inner message"),
+                    funds: vec![],
+                }),
+                reply_on: ReplyOn::Success,
+                gas_limit: None,
+            })
+            .add_event(
+                Event::new("my-collection-manager")
+                    .add_attribute("token-count-before", "3")
+                    .add_attribute("mint-payment-denom", "silver")
+                    .add_attribute("mint-payment-amount", "55"),
+            );
+        assert_eq!(received_response, expected_response);
+    }
+
+    #[test]
+    fn test_paid_mint_pass_through_second_denom() {
+        // Arrange
+        let mut mocked_deps_mut = mock_deps(NumTokensResponse { count: 3 });
+        let mocked_env = testing::mock_env();
+        let beneficiary = Addr::unchecked("beneficiary");
+        let deployer = Addr::unchecked("deployer");
+        let mocked_msg_info = testing::mock_info(&deployer.to_string(), &[]);
+        let instantiate_msg = InstantiateMsg {
+            payment_params: payment_params(
+                beneficiary.to_owned(),
+                vec![
+                    Coin {
+                        amount: Uint128::from(55u16),
+                        denom: "silver".to_owned(),
+                    },
+                    Coin {
+                        amount: Uint128::from(10u16),
+                        denom: "gold".to_owned(),
+                    },
+                ],
+            ),
+            owner: None,
+        };
+        let _ = super::instantiate(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            mocked_msg_info,
+            instantiate_msg,
+        )
+        .expect("Failed to instantiate manager");
+        let _ = super::sudo(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            SudoMsg::RegisterCollection {
+                collection: "collection".to_owned(),
+                label: "my names".to_owned(),
+            },
+        )
+        .expect("Failed to register collection");
+        let executer = Addr::unchecked("executer");
+        let fistful_silver = Coin {
+            amount: Uint128::from(30u16),
+            denom: "silver".to_owned(),
+        };
+        let mocked_msg_info = testing::mock_info(
+            &executer.to_string(),
+            &[
+                fistful_silver.to_owned(),
+                Coin {
+                    amount: Uint128::from(12u16),
+                    denom: "gold".to_owned(),
+                },
+            ],
+        );
+        let inner_msg = CollectionExecuteMsg::Mint {
+            token_id: "alice".to_owned(),
+            owner: "owner".to_owned(),
+            token_uri: None,
+            extension: None,
+        };
+        let execute_msg = ExecuteMsg::PassThrough {
+            collection: "collection".to_owned(),
+            message: inner_msg.to_owned(),
+            options: None,
+        };
+
+        // Act
+        let contract_result = super::execute(
+            mocked_deps_mut.as_mut(),
+            mocked_env,
+            mocked_msg_info,
+            execute_msg,
+        );
+
+        // Assert
+        assert!(contract_result.is_ok(), "Failed to pass message through");
+        let received_response = contract_result.unwrap();
+        let expected_response = Response::default()
+            .add_message(BankMsg::Send {
+                to_address: beneficiary.to_string(),
+                amount: vec![Coin {
+                    amount: Uint128::from(10u16),
+                    denom: "gold".to_owned(),
+                }],
+            })
+            .add_message(BankMsg::Send {
+                to_address: executer.to_string(),
+                amount: vec![
+                    fistful_silver,
+                    Coin {
+                        amount: Uint128::from(2u16),
+                        denom: "gold".to_owned(),
+                    },
+                ],
+            })
+            .add_submessage(SubMsg {
+                id: ReplyCode::PassThrough as u64,
+                msg: CosmosMsg::<Empty>::Wasm(WasmMsg::Execute {
+                    contract_addr: "collection".to_owned(),
+                    msg: to_json_binary(&inner_msg).expect("Failed to serialize inner message"),
+                    funds: vec![],
+                }),
+                reply_on: ReplyOn::Success,
+                gas_limit: None,
+            })
+            .add_event(
+                Event::new("my-collection-manager")
+                    .add_attribute("token-count-before", "3")
+                    .add_attribute("mint-payment-denom", "gold")
+                    .add_attribute("mint-payment-amount", "10"),
+            );
+        assert_eq!(received_response, expected_response);
+    }
+
+    #[test]
+    fn test_paid_mint_pass_through_missing_payment() {
+        // Arrange
+        let mut mocked_deps_mut = mock_deps(NumTokensResponse { count: 3 });
+        let mocked_env = testing::mock_env();
+        let deployer = Addr::unchecked("deployer");
+        let mocked_msg_info = testing::mock_info(&deployer.to_string(), &[]);
+        let instantiate_msg = InstantiateMsg {
+            payment_params: payment_params(
+                deployer.to_owned(),
+                vec![
+                    Coin {
+                        amount: Uint128::from(55u16),
+                        denom: "silver".to_owned(),
+                    },
+                    Coin {
+                        amount: Uint128::from(10u16),
+                        denom: "gold".to_owned(),
+                    },
+                ],
+            ),
+            owner: None,
+        };
+        let _ = super::instantiate(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            mocked_msg_info,
+            instantiate_msg,
+        )
+        .expect("Failed to instantiate manager");
+        let _ = super::sudo(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            SudoMsg::RegisterCollection {
+                collection: "collection".to_owned(),
+                label: "my names".to_owned(),
+            },
+        )
+        .expect("Failed to register collection");
+        let mocked_msg_info = testing::mock_info(
+            "executer",
+            &[Coin {
+                amount: Uint128::from(30u16),
+                denom: "silver".to_owned(),
+            }],
+        );
+        let execute_msg = ExecuteMsg::PassThrough {
+            collection: "collection".to_owned(),
+            message: CollectionExecuteMsg::Mint {
+                token_id: "alice".to_owned(),
+                owner: "owner".to_owned(),
+                token_uri: None,
+                extension: None,
+            },
+            options: None,
+        };
+
+        // Act
+        let contract_result = super::execute(
+            mocked_deps_mut.as_mut(),
+            mocked_env,
+            mocked_msg_info,
+            execute_msg,
+        );
+
+        // Assert
+        let expected_missing_payment = vec![
+            Coin {
+                amount: Uint128::from(25u16),
+                denom: "silver".to_owned(),
+            },
+            Coin {
+                amount: Uint128::from(10u16),
+                denom: "gold".to_owned(),
+            },
+        ];
+        assert!(
+            matches!(
+                contract_result,
+                Err(ContractError::MissingPayment { missing_payment })
+                    if missing_payment == expected_missing_payment
+            ),
+            "Should have reported the shortfall of each price"
+        );
+    }
+
+    #[test]
+    fn test_paid_mint_pass_through_split_among_beneficiaries() {
+        // Arrange
+        let mut mocked_deps_mut = mock_deps(NumTokensResponse { count: 3 });
+        let mocked_env = testing::mock_env();
+        let deployer = Addr::unchecked("deployer");
+        let mocked_msg_info = testing::mock_info(&deployer.to_string(), &[]);
+        let minting_price = Coin {
+            amount: Uint128::from(55u16),
+            denom: "silver".to_owned(),
+        };
+        let instantiate_msg = InstantiateMsg {
+            payment_params: PaymentParams {
+                beneficiaries: vec![
+                    Beneficiary {
+                        address: Addr::unchecked("creator"),
+                        share_bps: 5_000,
+                    },
+                    Beneficiary {
+                        address: Addr::unchecked("platform"),
+                        share_bps: 3_000,
+                    },
+                    Beneficiary {
+                        address: Addr::unchecked("community"),
+                        share_bps: 2_000,
+                    },
+                ],
+                mint_prices: vec![minting_price.to_owned()],
+                payout: PayoutMode::Send,
+                cw20_mint_price: None,
+                phases: vec![],
+                pricing: PricingRule::Fixed,
+                subscription: None,
+            },
+            owner: None,
+        };
+        let _ = super::instantiate(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            mocked_msg_info,
+            instantiate_msg,
+        )
+        .expect("Failed to instantiate manager");
+        let _ = super::sudo(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            SudoMsg::RegisterCollection {
+                collection: "collection".to_owned(),
+                label: "my names".to_owned(),
+            },
+        )
+        .expect("Failed to register collection");
+        let mocked_msg_info = testing::mock_info("executer", &[minting_price]);
+        let execute_msg = ExecuteMsg::PassThrough {
+            collection: "collection".to_owned(),
+            message: CollectionExecuteMsg::Mint {
+                token_id: "alice".to_owned(),
+                owner: "owner".to_owned(),
+                token_uri: None,
+                extension: None,
+            },
+            options: None,
+        };
+
+        // Act
+        let contract_result = super::execute(
+            mocked_deps_mut.as_mut(),
+            mocked_env,
+            mocked_msg_info,
+            execute_msg,
+        );
+
+        // Assert
+        assert!(contract_result.is_ok(), "Failed to pass message through");
+        let bank_msgs = contract_result
+            .unwrap()
+            .messages
+            .into_iter()
+            .filter_map(|sub_msg| match sub_msg.msg {
+                CosmosMsg::Bank(bank_msg) => Some(bank_msg),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let expected_bank_msgs = [
+            ("creator", 28u16),
+            ("platform", 16u16),
+            ("community", 11u16),
+        ]
+        .into_iter()
+        .map(|(to_address, amount)| BankMsg::Send {
+            to_address: to_address.to_owned(),
+            amount: vec![Coin {
+                amount: Uint128::from(amount),
+                denom: "silver".to_owned(),
+            }],
+        })
+        .collect::<Vec<_>>();
+        assert_eq!(bank_msgs, expected_bank_msgs);
+    }
+
+    #[test]
+    fn test_sudo_update_payment_params_rejects_bad_shares() {
+        // Arrange
+        let mut mocked_deps_mut = mock_deps(NumTokensResponse { count: 3 });
+        let mocked_env = testing::mock_env();
+        let update_msg = SudoMsg::UpdatePaymentParams(PaymentParams {
+            beneficiaries: vec![
+                Beneficiary {
+                    address: Addr::unchecked("creator"),
+                    share_bps: 5_000,
+                },
+                Beneficiary {
+                    address: Addr::unchecked("platform"),
+                    share_bps: 4_000,
+                },
+            ],
+            mint_prices: vec![],
+            payout: PayoutMode::Send,
+            cw20_mint_price: None,
+            phases: vec![],
+            pricing: PricingRule::Fixed,
+            subscription: None,
+        });
+
+        // Act
+        let contract_result = super::sudo(mocked_deps_mut.as_mut(), mocked_env, update_msg);
+
+        // Assert
+        assert!(
+            matches!(
+                contract_result,
+                Err(ContractError::InvalidShares { total_bps: 9_000 })
+            ),
+            "Should have rejected shares not adding up"
+        );
+    }
+
+    #[test]
+    fn test_paid_mint_pass_through_phases() {
+        // Arrange
+        let mut mocked_deps_mut = mock_deps(NumTokensResponse { count: 3 });
+        let mocked_env = testing::mock_env();
+        let deployer = Addr::unchecked("deployer");
+        let mocked_msg_info = testing::mock_info(&deployer.to_string(), &[]);
+        let presale_price = Coin {
+            amount: Uint128::from(10u16),
+            denom: "silver".to_owned(),
+        };
+        let instantiate_msg = InstantiateMsg {
+            payment_params: PaymentParams {
+                phases: vec![
+                    MintPhase {
+                        name: "presale".to_owned(),
+                        start: Scheduled::AtHeight(mocked_env.block.height - 100),
+                        end: Expiration::AtHeight(mocked_env.block.height + 100),
+                        mint_prices: vec![presale_price.to_owned()],
+                        cap: Some(1),
+                        max_per_wallet: None,
+                        allowlist_root: None,
+                    },
+                    MintPhase {
+                        name: "public".to_owned(),
+                        start: Scheduled::AtHeight(mocked_env.block.height + 100),
+                        end: Expiration::Never {},
+                        mint_prices: vec![Coin {
+                            amount: Uint128::from(20u16),
+                            denom: "silver".to_owned(),
+                        }],
+                        cap: None,
+                        max_per_wallet: None,
+                        allowlist_root: None,
+                    },
+                ],
+                ..payment_params(deployer.to_owned(), vec![])
+            },
+            owner: None,
+        };
+        let _ = super::instantiate(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            mocked_msg_info,
+            instantiate_msg,
+        )
+        .expect("Failed to instantiate manager");
+        let _ = super::sudo(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            SudoMsg::RegisterCollection {
+                collection: "collection".to_owned(),
+                label: "my names".to_owned(),
+            },
+        )
+        .expect("Failed to register collection");
+        let mocked_msg_info = testing::mock_info("executer", &[presale_price]);
+        let execute_msg = ExecuteMsg::PassThrough {
+            collection: "collection".to_owned(),
+            message: CollectionExecuteMsg::Mint {
+                token_id: "alice".to_owned(),
+                owner: "owner".to_owned(),
+                token_uri: None,
+                extension: None,
+            },
+            options: None,
+        };
+        let mut early_env = mocked_env.to_owned();
+        early_env.block.height -= 101;
+
+        // Act
+        let contract_result = super::execute(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            mocked_msg_info.to_owned(),
+            execute_msg.to_owned(),
+        );
+
+        // Assert
+        assert!(contract_result.is_ok(), "Failed to mint during presale");
+        let expected_event = Event::new("my-collection-manager")
+            .add_attribute("token-count-before", "3")
+            .add_attribute("mint-phase", "presale")
+            .add_attribute("mint-payment-denom", "silver")
+            .add_attribute("mint-payment-amount", "10");
+        assert_eq!(contract_result.unwrap().events, vec![expected_event]);
+        let capped_result = super::execute(
+            mocked_deps_mut.as_mut(),
+            mocked_env,
+            mocked_msg_info.to_owned(),
+            execute_msg.to_owned(),
+        );
+        assert!(
+            matches!(
+                capped_result,
+                Err(ContractError::PhaseCapReached { phase }) if phase == "presale"
+            ),
+            "Should have stopped at the presale cap"
+        );
+        let early_result = super::execute(
+            mocked_deps_mut.as_mut(),
+            early_env,
+            mocked_msg_info,
+            execute_msg,
+        );
+        assert!(
+            matches!(early_result, Err(ContractError::NoActivePhase)),
+            "Should have rejected a mint before any phase"
+        );
+    }
+
+    #[test]
+    fn test_dutch_auction_mint_pass_through() {
+        // Arrange
+        let mut mocked_deps_mut = mock_deps(NumTokensResponse { count: 3 });
+        let mocked_env = testing::mock_env();
+        let deployer = Addr::unchecked("deployer");
+        let mocked_msg_info = testing::mock_info(&deployer.to_string(), &[]);
+        let instantiate_msg = InstantiateMsg {
+            payment_params: PaymentParams {
+                pricing: PricingRule::DutchAuction(DutchAuction {
+                    start_price: Coin {
+                        amount: Uint128::from(100u16),
+                        denom: "silver".to_owned(),
+                    },
+                    floor_amount: Uint128::from(40u16),
+                    start_time: mocked_env.block.time.minus_seconds(150),
+                    decay_step: Uint128::from(10u16),
+                    decay_interval: 60,
+                    rebate: false,
+                }),
+                ..payment_params(deployer.to_owned(), vec![])
+            },
+            owner: None,
+        };
+        let _ = super::instantiate(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            mocked_msg_info,
+            instantiate_msg,
+        )
+        .expect("Failed to instantiate manager");
+        let _ = super::sudo(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            SudoMsg::RegisterCollection {
+                collection: "collection".to_owned(),
+                label: "my names".to_owned(),
+            },
+        )
+        .expect("Failed to register collection");
+        let mocked_msg_info = testing::mock_info(
+            "executer",
+            &[Coin {
+                amount: Uint128::from(100u16),
+                denom: "silver".to_owned(),
+            }],
+        );
+        let execute_msg = ExecuteMsg::PassThrough {
+            collection: "collection".to_owned(),
+            message: CollectionExecuteMsg::Mint {
+                token_id: "alice".to_owned(),
+                owner: "owner".to_owned(),
+                token_uri: None,
+                extension: None,
+            },
+            options: None,
+        };
+        let mut late_env = mocked_env.to_owned();
+        late_env.block.time = late_env.block.time.plus_seconds(3_600);
+
+        // Act
+        let contract_result = super::execute(
+            mocked_deps_mut.as_mut(),
+            mocked_env,
+            mocked_msg_info,
+            execute_msg,
+        );
+
+        // Assert
+        assert!(contract_result.is_ok(), "Failed to mint during the auction");
+        let received_response = contract_result.unwrap();
+        assert_eq!(
+            received_response.messages[..2],
+            vec![
+                SubMsg::new(BankMsg::Send {
+                    to_address: deployer.to_string(),
+                    amount: vec![Coin {
+                        amount: Uint128::from(80u16),
+                        denom: "silver".to_owned(),
+                    }],
+                }),
+                SubMsg::new(BankMsg::Send {
+                    to_address: "executer".to_owned(),
+                    amount: vec![Coin {
+                        amount: Uint128::from(20u16),
+                        denom: "silver".to_owned(),
+                    }],
+                }),
+            ]
+        );
+        let late_price = from_json::<CurrentMintPriceResponse>(
+            super::query(
+                mocked_deps_mut.as_ref(),
+                late_env,
+                QueryMsg::CurrentMintPrice {
+                    collection: "collection".to_owned(),
+                },
+            )
+            .expect("Failed to query the current mint price"),
+        )
+        .expect("Failed to deserialize the current mint price");
+        assert_eq!(
+            late_price,
+            CurrentMintPriceResponse {
+                mint_prices: vec![Coin {
+                    amount: Uint128::from(40u16),
+                    denom: "silver".to_owned(),
+                }],
+                phase: None,
+            },
+            "Should have stopped at the floor"
+        );
+    }
+
+    #[test]
+    fn test_bonding_curve_mint_pass_through() {
+        // Arrange
+        let mut mocked_deps_mut = mock_deps(NumTokensResponse { count: 3 });
+        let mocked_env = testing::mock_env();
+        let deployer = Addr::unchecked("deployer");
+        let mocked_msg_info = testing::mock_info(&deployer.to_string(), &[]);
+        let instantiate_msg = InstantiateMsg {
+            payment_params: PaymentParams {
+                pricing: PricingRule::BondingCurve(BondingCurve {
+                    denom: "silver".to_owned(),
+                    curve: Curve::Linear {
+                        base: Uint128::from(10u16),
+                        slope: Uint128::from(5u16),
+                    },
+                }),
+                ..payment_params(deployer.to_owned(), vec![])
+            },
+            owner: None,
+        };
+        let _ = super::instantiate(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            mocked_msg_info,
+            instantiate_msg,
+        )
+        .expect("Failed to instantiate manager");
+        let _ = super::sudo(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            SudoMsg::RegisterCollection {
+                collection: "collection".to_owned(),
+                label: "my names".to_owned(),
+            },
+        )
+        .expect("Failed to register collection");
+        let mocked_msg_info = testing::mock_info(
+            "executer",
+            &[Coin {
+                amount: Uint128::from(25u16),
+                denom: "silver".to_owned(),
+            }],
+        );
+        let execute_msg = ExecuteMsg::PassThrough {
+            collection: "collection".to_owned(),
+            message: CollectionExecuteMsg::Mint {
+                token_id: "alice".to_owned(),
+                owner: "owner".to_owned(),
+                token_uri: None,
+                extension: None,
+            },
+            options: None,
+        };
+
+        // Act
+        let contract_result = super::execute(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            mocked_msg_info,
+            execute_msg,
+        );
+
+        // Assert
+        assert!(contract_result.is_ok(), "Failed to mint on the curve");
+        let expected_event = Event::new("my-collection-manager")
+            .add_attribute("token-count-before", "3")
+            .add_attribute("mint-payment-denom", "silver")
+            .add_attribute("mint-payment-amount", "25");
+        assert_eq!(contract_result.unwrap().events, vec![expected_event]);
+        let quote = from_json::<QuoteMintResponse>(
+            super::query(
+                mocked_deps_mut.as_ref(),
+                mocked_env.to_owned(),
+                QueryMsg::QuoteMint {
+                    collection: "collection".to_owned(),
+                    quantity: 3,
+                },
+            )
+            .expect("Failed to quote mints"),
+        )
+        .expect("Failed to deserialize the quote");
+        assert_eq!(
+            quote,
+            QuoteMintResponse {
+                totals: vec![Coin {
+                    amount: Uint128::from(90u16),
+                    denom: "silver".to_owned(),
+                }],
+            }
+        );
+        let flat_result = super::sudo(
+            mocked_deps_mut.as_mut(),
+            mocked_env,
+            SudoMsg::UpdatePaymentParams(PaymentParams {
+                pricing: PricingRule::BondingCurve(BondingCurve {
+                    denom: "silver".to_owned(),
+                    curve: Curve::Piecewise {
+                        pieces: vec![
+                            CurvePiece {
+                                from_supply: 0,
+                                price: Uint128::from(10u16),
+                            },
+                            CurvePiece {
+                                from_supply: 5,
+                                price: Uint128::from(10u16),
+                            },
+                        ],
+                    },
+                }),
+                ..payment_params(deployer, vec![])
+            }),
+        );
+        assert!(
+            matches!(flat_result, Err(ContractError::InvalidCurvePieces)),
+            "Should have rejected pieces without a rising price"
+        );
+    }
+
+    #[test]
+    fn test_exponential_curve_price_at() {
+        // Arrange
+        let bonding_curve = BondingCurve {
+            denom: "silver".to_owned(),
+            curve: Curve::Exponential {
+                base: Uint128::from(100u16),
+                growth_bps: 1_000,
+            },
+        };
+
+        // Act
+        let price = bonding_curve.price_at(3);
+        let overflow_result = bonding_curve.price_at(1_000);
+
+        // Assert
+        assert_eq!(
+            price.expect("Failed to price on the curve"),
+            Coin {
+                amount: Uint128::from(133u16),
+                denom: "silver".to_owned(),
+            }
+        );
+        assert!(
+            matches!(overflow_result, Err(StdError::Overflow { .. })),
+            "Should have overflowed far along the curve"
+        );
+    }
+
+    #[test]
+    fn test_name_length_mint_pass_through() {
+        // Arrange
+        let mut mocked_deps_mut = mock_deps(NumTokensResponse { count: 3 });
+        let mocked_env = testing::mock_env();
+        let deployer = Addr::unchecked("deployer");
+        let mocked_msg_info = testing::mock_info(&deployer.to_string(), &[]);
+        let tier = |min_length: u32, price: u16| LengthTier {
+            min_length,
+            price: Uint128::from(price),
+        };
+        let instantiate_msg = InstantiateMsg {
+            payment_params: PaymentParams {
+                pricing: PricingRule::NameLength(NameLengthPricing {
+                    denom: "silver".to_owned(),
+                    tiers: vec![tier(1, 100), tier(3, 50), tier(5, 10)],
+                    premium_names: vec![PremiumName {
+                        name: "bob".to_owned(),
+                        price: Uint128::from(500u16),
+                    }],
+                }),
+                ..payment_params(deployer.to_owned(), vec![])
+            },
+            owner: None,
+        };
+        let _ = super::instantiate(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            mocked_msg_info,
+            instantiate_msg,
+        )
+        .expect("Failed to instantiate manager");
+        let _ = super::sudo(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            SudoMsg::RegisterCollection {
+                collection: "collection".to_owned(),
+                label: "my names".to_owned(),
+            },
+        )
+        .expect("Failed to register collection");
+        let mocked_msg_info = testing::mock_info(
+            "executer",
+            &[Coin {
+                amount: Uint128::from(100u16),
+                denom: "silver".to_owned(),
+            }],
+        );
+        let mint_msg = |name: &str| ExecuteMsg::PassThrough {
+            collection: "collection".to_owned(),
+            message: CollectionExecuteMsg::Mint {
+                token_id: name.to_owned(),
+                owner: "owner".to_owned(),
+                token_uri: None,
+                extension: None,
+            },
+            options: None,
+        };
+
+        // Act
+        let contract_result = super::execute(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            mocked_msg_info.to_owned(),
+            mint_msg("al"),
+        );
+
+        // Assert
+        assert!(contract_result.is_ok(), "Failed to mint a short name");
+        let expected_event = Event::new("my-collection-manager")
+            .add_attribute("token-count-before", "3")
+            .add_attribute("mint-payment-denom", "silver")
+            .add_attribute("mint-payment-amount", "100");
+        assert_eq!(contract_result.unwrap().events, vec![expected_event]);
+        let premium_result = super::execute(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            mocked_msg_info,
+            mint_msg("bob"),
+        );
+        assert!(
+            matches!(
+                premium_result,
+                Err(ContractError::MissingPayment { missing_payment })
+                    if missing_payment == vec![Coin {
+                        amount: Uint128::from(400u16),
+                        denom: "silver".to_owned(),
+                    }]
+            ),
+            "Should have charged the premium price"
+        );
+        let quote = from_json::<QuoteNameResponse>(
+            super::query(
+                mocked_deps_mut.as_ref(),
+                mocked_env,
+                QueryMsg::QuoteName {
+                    collection: "collection".to_owned(),
+                    name: "alice".to_owned(),
+                },
+            )
+            .expect("Failed to quote name"),
+        )
+        .expect("Failed to deserialize the quote");
+        assert_eq!(
+            quote,
+            QuoteNameResponse {
+                mint_prices: vec![Coin {
+                    amount: Uint128::from(10u16),
+                    denom: "silver".to_owned(),
+                }],
+                premium: false,
+            }
+        );
+    }
+
+    #[test]
+    fn test_name_rules_mint_pass_through() {
+        // Arrange
+        let mut mocked_deps_mut = mock_deps(NumTokensResponse { count: 3 });
+        let mocked_env = testing::mock_env();
+        let deployer = Addr::unchecked("deployer");
+        let mocked_msg_info = testing::mock_info(&deployer.to_string(), &[]);
+        let instantiate_msg = InstantiateMsg {
+            payment_params: payment_params(deployer.to_owned(), vec![]),
+            owner: None,
+        };
+        let _ = super::instantiate(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            mocked_msg_info.to_owned(),
+            instantiate_msg,
+        )
+        .expect("Failed to instantiate manager");
+        let _ = super::sudo(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            SudoMsg::RegisterCollection {
+                collection: "collection".to_owned(),
+                label: "my names".to_owned(),
+            },
+        )
+        .expect("Failed to register collection");
+        let _ = super::sudo(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            SudoMsg::SetNameRules {
+                collection: "collection".to_owned(),
+                rules: NameRules {
+                    charset: Charset::Unicode,
+                    min_length: 3,
+                    max_length: 10,
+                    reserved_names: vec!["admin".to_owned()],
+                },
+            },
+        )
+        .expect("Failed to set name rules");
+        let mint_msg = |name: &str| ExecuteMsg::PassThrough {
+            collection: "collection".to_owned(),
+            message: CollectionExecuteMsg::Mint {
+                token_id: name.to_owned(),
+                owner: "owner".to_owned(),
+                token_uri: None,
+                extension: None,
+            },
+            options: None,
+        };
+        let executer_info = testing::mock_info("executer", &[]);
+
+        // Act
+        let results = [
+            "al",
+            "-alice",
+            "p\u{430}ypal",
+            "\u{ff50}ay",
+            "Alice",
+            "\u{1c5}ab",
+            "\u{430}\u{440}\u{435}",
+            "admin",
+        ]
+        .map(|name| {
+            super::execute(
+                mocked_deps_mut.as_mut(),
+                mocked_env.to_owned(),
+                executer_info.to_owned(),
+                mint_msg(name),
+            )
+        });
+
+        // Assert
+        assert!(matches!(
+            results,
+            [
+                Err(ContractError::NameTooShort { min_length: 3 }),
+                Err(ContractError::NameHyphenAtEdge),
+                Err(ContractError::MixedScripts),
+                Err(ContractError::NameNotNormalized),
+                Err(ContractError::InvalidNameCharacter { character: 'A' }),
+                Err(ContractError::InvalidNameCharacter {
+                    character: '\u{1c5}'
+                }),
+                Err(ContractError::WholeScriptConfusable),
+                Err(ContractError::NameReserved { .. }),
+            ]
+        ));
+        let owner_result = super::execute(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            mocked_msg_info,
+            mint_msg("admin"),
+        );
+        assert!(owner_result.is_ok(), "Owner should mint a reserved name");
+        let unicode_result = super::execute(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            executer_info.to_owned(),
+            mint_msg("\u{e9}t\u{e9}-2"),
+        );
+        assert!(unicode_result.is_ok(), "Failed to mint a Unicode name");
+        let cyrillic_result = super::execute(
+            mocked_deps_mut.as_mut(),
+            mocked_env,
+            executer_info,
+            mint_msg("\u{43c}\u{438}\u{440}"),
+        );
+        assert!(cyrillic_result.is_ok(), "Failed to mint a Cyrillic name");
+    }
+
+    #[test]
+    fn test_subscription_mint_renew_and_reclaim() {
+        // Arrange
+        let mut mocked_deps_mut = mock_deps(NumTokensResponse { count: 3 });
+        let mocked_env = testing::mock_env();
+        let deployer = Addr::unchecked("deployer");
+        let mocked_msg_info = testing::mock_info(&deployer.to_string(), &[]);
+        let yearly_price = |years: u16| Coin {
+            amount: Uint128::from(10u16 * years),
+            denom: "silver".to_owned(),
+        };
+        let instantiate_msg = InstantiateMsg {
+            payment_params: PaymentParams {
+                subscription: Some(Subscription {
+                    max_years: 3,
+                    grace_period: 1_000,
+                }),
+                ..payment_params(deployer.to_owned(), vec![yearly_price(1)])
+            },
+            owner: None,
+        };
+        let _ = super::instantiate(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            mocked_msg_info.to_owned(),
+            instantiate_msg,
+        )
+        .expect("Failed to instantiate manager");
+        let _ = super::sudo(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            SudoMsg::RegisterCollection {
+                collection: "collection".to_owned(),
+                label: "my names".to_owned(),
+            },
+        )
+        .expect("Failed to register collection");
+        let execute_msg = ExecuteMsg::PassThrough {
+            collection: "collection".to_owned(),
+            message: CollectionExecuteMsg::Mint {
+                token_id: "alice".to_owned(),
+                owner: "owner".to_owned(),
+                token_uri: None,
+                extension: None,
+            },
+            options: Some(PassThroughOptions {
+                allowlist: None,
+                years: Some(2),
+                catch_errors: false,
+                return_data: None,
+            }),
+        };
+        let expires_at = mocked_env.block.time.plus_seconds(2 * SECONDS_PER_YEAR);
+
+        // Act
+        let contract_result = super::execute(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            testing::mock_info("executer", &[yearly_price(2)]),
+            execute_msg,
+        );
+
+        // Assert
+        assert!(contract_result.is_ok(), "Failed to mint for two years");
+        let expected_event = Event::new("my-collection-manager")
+            .add_attribute("token-count-before", "3")
+            .add_attribute("mint-expires-at", expires_at.seconds().to_string())
+            .add_attribute("mint-payment-denom", "silver")
+            .add_attribute("mint-payment-amount", "20");
+        assert_eq!(contract_result.unwrap().events, vec![expected_event]);
+        let mut grace_env = mocked_env.to_owned();
+        grace_env.block.time = expires_at.plus_seconds(1_000);
+        let renew_result = super::execute(
+            mocked_deps_mut.as_mut(),
+            grace_env.to_owned(),
+            testing::mock_info("executer", &[yearly_price(1)]),
+            ExecuteMsg::Renew {
+                collection: "collection".to_owned(),
+                token_id: "alice".to_owned(),
+                years: 1,
+            },
+        );
+        assert!(
+            renew_result.is_ok(),
+            "Failed to renew during the grace period"
+        );
+        let unregistered_result = super::execute(
+            mocked_deps_mut.as_mut(),
+            grace_env.to_owned(),
+            testing::mock_info("executer", &[yearly_price(1)]),
+            ExecuteMsg::Renew {
+                collection: "other-collection".to_owned(),
+                token_id: "alice".to_owned(),
+                years: 1,
+            },
+        );
+        assert!(
+            matches!(
+                unregistered_result,
+                Err(ContractError::CollectionNotRegistered { .. })
+            ),
+            "Should only renew on registered collections"
+        );
+        let expires_at = expires_at.plus_seconds(SECONDS_PER_YEAR);
+        let mut expired_env = mocked_env.to_owned();
+        expired_env.block.time = expires_at.plus_seconds(1_001);
+        let early_result = super::execute(
+            mocked_deps_mut.as_mut(),
+            grace_env,
+            mocked_msg_info,
+            ExecuteMsg::ReclaimExpired {
+                collection: "collection".to_owned(),
+                token_id: "alice".to_owned(),
+            },
+        );
+        assert!(
+            matches!(
+                early_result,
+                Err(ContractError::NameNotExpired { grace_ends_at, .. })
+                    if grace_ends_at == expires_at.plus_seconds(1_000)
+            ),
+            "Should not reclaim a renewed name"
+        );
+        let keeperless_result = super::execute(
+            mocked_deps_mut.as_mut(),
+            expired_env,
+            testing::mock_info("executer", &[]),
+            ExecuteMsg::ReclaimExpired {
+                collection: "collection".to_owned(),
+                token_id: "alice".to_owned(),
+            },
+        );
+        assert!(
+            matches!(
+                keeperless_result,
+                Err(ContractError::MissingRole {
+                    role: Role::Keeper,
+                    ..
+                })
+            ),
+            "Should only reclaim as keeper"
+        );
+    }
+
+    #[test]
+    fn test_pass_through_batch() {
+        // Arrange
+        let mut mocked_deps_mut = mock_deps(NumTokensResponse { count: 3 });
+        let mocked_env = testing::mock_env();
+        let deployer = Addr::unchecked("deployer");
+        let mocked_msg_info = testing::mock_info(&deployer.to_string(), &[]);
+        let silver = |amount: u16| Coin {
+            amount: Uint128::from(amount),
+            denom: "silver".to_owned(),
+        };
+        let instantiate_msg = InstantiateMsg {
+            payment_params: payment_params(deployer.to_owned(), vec![silver(10)]),
+            owner: None,
+        };
+        let _ = super::instantiate(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            mocked_msg_info,
+            instantiate_msg,
+        )
+        .expect("Failed to instantiate manager");
+        let _ = super::sudo(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            SudoMsg::RegisterCollection {
+                collection: "collection".to_owned(),
+                label: "my names".to_owned(),
+            },
+        )
+        .expect("Failed to register collection");
+        let mint_item = |name: &str| PassThroughItem {
+            collection: "collection".to_owned(),
+            message: CollectionExecuteMsg::Mint {
+                token_id: name.to_owned(),
+                owner: "owner".to_owned(),
+                token_uri: None,
+                extension: None,
+            },
+            options: None,
+        };
+        let execute_msg = ExecuteMsg::PassThroughBatch {
+            items: vec![mint_item("alice"), mint_item("bob")],
+            mode: BatchMode::Atomic,
+        };
+
+        // Act
+        let contract_result = super::execute(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            testing::mock_info("executer", &[silver(25)]),
+            execute_msg.to_owned(),
+        );
+
+        // Assert
+        assert!(contract_result.is_ok(), "Failed to pass the batch through");
+        let received_response = contract_result.unwrap();
+        let bank_send = |to_address: &str, amount: u16| {
+            SubMsg::new(BankMsg::Send {
+                to_address: to_address.to_owned(),
+                amount: vec![silver(amount)],
+            })
+        };
+        assert_eq!(
+            received_response.messages[..3],
+            vec![
+                bank_send("deployer", 10),
+                bank_send("deployer", 10),
+                bank_send("executer", 5),
+            ]
+        );
+        assert_eq!(received_response.messages.len(), 5);
+        let expected_event = Event::new("my-collection-manager")
+            .add_attribute("batch-item", "0")
+            .add_attribute("batch-item-collection", "collection")
+            .add_attribute("token-count-before", "3")
+            .add_attribute("mint-payment-denom", "silver")
+            .add_attribute("mint-payment-amount", "10")
+            .add_attribute("batch-item", "1")
+            .add_attribute("batch-item-collection", "collection")
+            .add_attribute("token-count-before", "4")
+            .add_attribute("mint-payment-denom", "silver")
+            .add_attribute("mint-payment-amount", "10");
+        assert_eq!(received_response.events, vec![expected_event]);
+        let underpaid_result = super::execute(
+            mocked_deps_mut.as_mut(),
+            mocked_env,
+            testing::mock_info("executer", &[silver(15)]),
+            execute_msg,
+        );
+        assert!(
+            matches!(
+                underpaid_result,
+                Err(ContractError::MissingPayment { missing_payment })
+                    if missing_payment == vec![silver(5)]
+            ),
+            "Should have priced every mint of the batch"
+        );
+    }
+
+    #[test]
+    fn test_best_effort_pass_through_batch() {
+        // Arrange
+        let mut mocked_deps_mut = mock_deps(NumTokensResponse { count: 3 });
+        let mocked_env = testing::mock_env();
+        let deployer = Addr::unchecked("deployer");
+        let mocked_msg_info = testing::mock_info(&deployer.to_string(), &[]);
+        let silver = |amount: u16| Coin {
+            amount: Uint128::from(amount),
+            denom: "silver".to_owned(),
+        };
+        let instantiate_msg = InstantiateMsg {
+            payment_params: payment_params(deployer.to_owned(), vec![silver(10)]),
+            owner: None,
+        };
+        let _ = super::instantiate(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            mocked_msg_info,
+            instantiate_msg,
+        )
+        .expect("Failed to instantiate manager");
+        let _ = super::sudo(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            SudoMsg::RegisterCollection {
+                collection: "collection".to_owned(),
+                label: "my names".to_owned(),
+            },
+        )
+        .expect("Failed to register collection");
+        let mint_item = |name: &str| PassThroughItem {
+            collection: "collection".to_owned(),
+            message: CollectionExecuteMsg::Mint {
+                token_id: name.to_owned(),
+                owner: "owner".to_owned(),
+                token_uri: None,
+                extension: None,
+            },
+            options: None,
+        };
+        let execute_msg = ExecuteMsg::PassThroughBatch {
+            items: vec![mint_item("alice"), mint_item("bob")],
+            mode: BatchMode::BestEffort,
+        };
+        let bank_send = |to_address: &str, amount: u16| {
+            SubMsg::new(BankMsg::Send {
+                to_address: to_address.to_owned(),
+                amount: vec![silver(amount)],
+            })
+        };
+
+        // Act
+        let contract_result = super::execute(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            testing::mock_info("executer", &[silver(20)]),
+            execute_msg,
+        );
+
+        // Assert
+        assert!(contract_result.is_ok(), "Failed to pass the batch through");
+        let received_response = contract_result.unwrap();
+        assert_eq!(
+            received_response.messages.len(),
+            2,
+            "Should hold the payments"
+        );
+        assert_eq!(
+            received_response
+                .messages
+                .iter()
+                .map(|sub_msg| (sub_msg.id, sub_msg.reply_on.to_owned()))
+                .collect::<Vec<_>>(),
+            vec![
+                (ReplyCode::BatchItem as u64, ReplyOn::Always),
+                (ReplyCode::BatchItem as u64 + 1, ReplyOn::Always),
+            ]
+        );
+        let succeeded_response = super::reply(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            Reply {
+                id: ReplyCode::BatchItem as u64,
+                result: SubMsgResult::Ok(SubMsgResponse {
+                    data: None,
+                    events: vec![],
+                }),
+            },
+        )
+        .expect("Failed to handle the reply of the first item");
+        assert_eq!(succeeded_response.messages, vec![bank_send("deployer", 10)]);
+        assert_eq!(succeeded_response.data, None);
+        let failed_response = super::reply(
+            mocked_deps_mut.as_mut(),
+            mocked_env,
+            Reply {
+                id: ReplyCode::BatchItem as u64 + 1,
+                result: SubMsgResult::Err("token_id already claimed".to_owned()),
+            },
+        )
+        .expect("Failed to handle the reply of the second item");
+        assert_eq!(failed_response.messages, vec![bank_send("executer", 10)]);
+        let expected_event = Event::new("my-collection-manager")
+            .add_attribute("batch-item", "1")
+            .add_attribute("batch-item-collection", "collection")
+            .add_attribute("batch-item-status", "failed")
+            .add_attribute("batch-item-error", "token_id already claimed")
+            .add_attribute("batch-item-refund-denom", "silver")
+            .add_attribute("batch-item-refund-amount", "10");
+        assert_eq!(failed_response.events, vec![expected_event]);
+        assert_eq!(
+            from_json::<BatchResponse>(failed_response.data.expect("Should return the result"))
+                .expect("Failed to parse the batch result"),
+            BatchResponse {
+                succeeded: vec![0],
+                failed: vec![BatchItemFailure {
+                    index: 1,
+                    error: "token_id already claimed".to_owned(),
+                }],
+            }
+        );
+        let collection = Addr::unchecked("collection");
+        let minter = Addr::unchecked("executer");
+        assert_eq!(
+            WALLET_MINTS.load(&mocked_deps_mut.storage, (&collection, &minter)),
+            Ok(1),
+            "Should have given the failed mint back"
+        );
+        assert!(PENDING_BATCHES
+            .may_load(&mocked_deps_mut.storage, 0)
+            .expect("Failed to load pending batch")
+            .is_none());
+    }
+
+    #[test]
+    fn test_best_effort_batch_passed_through_while_another_is_pending() {
+        // Arrange
+        let mut mocked_deps_mut = mock_deps(NumTokensResponse { count: 3 });
+        let mocked_env = testing::mock_env();
+        let deployer = Addr::unchecked("deployer");
+        let instantiate_msg = InstantiateMsg {
+            payment_params: payment_params(deployer.to_owned(), vec![]),
+            owner: None,
+        };
+        let _ = super::instantiate(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            testing::mock_info(deployer.as_ref(), &[]),
+            instantiate_msg,
+        )
+        .expect("Failed to instantiate manager");
+        let _ = super::sudo(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            SudoMsg::RegisterCollection {
+                collection: "collection".to_owned(),
+                label: "my names".to_owned(),
+            },
+        )
+        .expect("Failed to register collection");
+        let batch_msg = |names: &[&str]| ExecuteMsg::PassThroughBatch {
+            items: names
+                .iter()
+                .map(|name| PassThroughItem {
+                    collection: "collection".to_owned(),
+                    message: CollectionExecuteMsg::Mint {
+                        token_id: (*name).to_owned(),
+                        owner: "owner".to_owned(),
+                        token_uri: None,
+                        extension: None,
+                    },
+                    options: None,
+                })
+                .collect(),
+            mode: BatchMode::BestEffort,
+        };
+        let _ = super::execute(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            testing::mock_info("executer", &[]),
+            batch_msg(&["alice", "bob"]),
+        )
+        .expect("Failed to pass the first batch through");
+        let reply_ok = |id: u64| Reply {
+            id,
+            result: SubMsgResult::Ok(SubMsgResponse {
+                data: None,
+                events: vec![],
+            }),
+        };
+
+        // Act
+        let second_response = super::execute(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            testing::mock_info("other-executer", &[]),
+            batch_msg(&["carol"]),
+        )
+        .expect("Failed to pass the second batch through");
+
+        // Assert
+        let second_id = ReplyCode::BatchItem as u64 + MAX_BATCH_ITEMS as u64;
+        assert_eq!(
+            second_response
+                .messages
+                .iter()
+                .map(|sub_msg| sub_msg.id)
+                .collect::<Vec<_>>(),
+            vec![second_id]
+        );
+        let second_reply = super::reply(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            Reply {
+                id: second_id,
+                result: SubMsgResult::Err("token_id already claimed".to_owned()),
+            },
+        )
+        .expect("Failed to handle the reply of the second batch");
+        assert_eq!(
+            from_json::<BatchResponse>(second_reply.data.expect("Should return the result"))
+                .expect("Failed to parse the batch result"),
+            BatchResponse {
+                succeeded: vec![],
+                failed: vec![BatchItemFailure {
+                    index: 0,
+                    error: "token_id already claimed".to_owned(),
+                }],
+            }
+        );
+        let _ = super::reply(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            reply_ok(ReplyCode::BatchItem as u64),
+        )
+        .expect("Failed to handle the first reply of the first batch");
+        let first_reply = super::reply(
+            mocked_deps_mut.as_mut(),
+            mocked_env,
+            reply_ok(ReplyCode::BatchItem as u64 + 1),
+        )
+        .expect("Failed to handle the second reply of the first batch");
+        assert_eq!(
+            from_json::<BatchResponse>(first_reply.data.expect("Should return the result"))
+                .expect("Failed to parse the batch result"),
+            BatchResponse {
+                succeeded: vec![0, 1],
+                failed: vec![],
+            }
+        );
+        let collection = Addr::unchecked("collection");
+        assert_eq!(
+            WALLET_MINTS.may_load(
+                &mocked_deps_mut.storage,
+                (&collection, &Addr::unchecked("other-executer"))
+            ),
+            Ok(Some(0)),
+            "Should have given the failed mint of the second batch back"
+        );
+        assert!(PENDING_BATCHES.is_empty(&mocked_deps_mut.storage));
+    }
+
+    #[test]
+    fn test_pass_through_catching_errors() {
+        // Arrange
+        let mut mocked_deps_mut = mock_deps(NumTokensResponse { count: 3 });
+        let mocked_env = testing::mock_env();
+        let deployer = Addr::unchecked("deployer");
+        let mocked_msg_info = testing::mock_info(&deployer.to_string(), &[]);
+        let silver = |amount: u16| Coin {
+            amount: Uint128::from(amount),
+            denom: "silver".to_owned(),
+        };
+        let instantiate_msg = InstantiateMsg {
+            payment_params: payment_params(deployer.to_owned(), vec![silver(10)]),
+            owner: None,
+        };
+        let _ = super::instantiate(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            mocked_msg_info,
+            instantiate_msg,
+        )
+        .expect("Failed to instantiate manager");
+        let _ = super::sudo(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            SudoMsg::RegisterCollection {
+                collection: "collection".to_owned(),
+                label: "my names".to_owned(),
+            },
+        )
+        .expect("Failed to register collection");
+        let mint_msg = |name: &str| ExecuteMsg::PassThrough {
+            collection: "collection".to_owned(),
+            message: CollectionExecuteMsg::Mint {
+                token_id: name.to_owned(),
+                owner: "owner".to_owned(),
+                token_uri: None,
+                extension: None,
+            },
+            options: Some(PassThroughOptions {
+                allowlist: None,
+                years: None,
+                catch_errors: true,
+                return_data: None,
+            }),
+        };
+
+        // Act
+        let contract_result = super::execute(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            testing::mock_info("executer", &[silver(10)]),
+            mint_msg("alice"),
+        );
+
+        // Assert
+        assert!(contract_result.is_ok(), "Failed to pass the mint through");
+        let received_response = contract_result.unwrap();
+        assert_eq!(
+            received_response.messages.len(),
+            1,
+            "Should hold the payment"
+        );
+        assert_eq!(
+            received_response.messages[0].id,
+            ReplyCode::PendingPassThrough as u64
+        );
+        assert_eq!(received_response.messages[0].reply_on, ReplyOn::Always);
+        let other_response = super::execute(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            testing::mock_info("other-executer", &[silver(10)]),
+            mint_msg("bob"),
+        )
+        .expect("Failed to pass another mint through");
+        assert_eq!(
+            other_response.messages[0].id,
+            ReplyCode::PendingPassThrough as u64 + 1,
+            "Should not share the reply of the pending pass-through"
+        );
+        let reply_result = super::reply(
+            mocked_deps_mut.as_mut(),
+            mocked_env,
+            Reply {
+                id: ReplyCode::PendingPassThrough as u64,
+                result: SubMsgResult::Err("token_id already claimed".to_owned()),
+            },
+        );
+        assert!(reply_result.is_ok(), "Should have caught the error");
+        let reply_response = reply_result.unwrap();
+        assert_eq!(
+            reply_response.messages,
+            vec![SubMsg::new(BankMsg::Send {
+                to_address: "executer".to_owned(),
+                amount: vec![silver(10)],
+            })]
+        );
+        let expected_event = Event::new("my-collection-manager")
+            .add_attribute("pass-through-failed-collection", "collection")
+            .add_attribute("pass-through-failed-kind", "mint")
+            .add_attribute("pass-through-error", "token_id already claimed")
+            .add_attribute("pass-through-refund-denom", "silver")
+            .add_attribute("pass-through-refund-amount", "10");
+        assert_eq!(reply_response.events, vec![expected_event]);
+        assert_eq!(
+            from_json::<PassThroughFailure>(
+                reply_response.data.expect("Should explain the failure")
+            )
+            .expect("Failed to parse the failure"),
+            PassThroughFailure {
+                collection: Addr::unchecked("collection"),
+                kind: CollectionMsgKind::Mint,
+                error: "token_id already claimed".to_owned(),
+            }
+        );
+        let collection = Addr::unchecked("collection");
+        let minter = Addr::unchecked("executer");
+        assert_eq!(
+            WALLET_MINTS.load(&mocked_deps_mut.storage, (&collection, &minter)),
+            Ok(0),
+            "Should have given the failed mint back"
+        );
+        assert_eq!(
+            PENDING_PASS_THROUGHS
+                .keys(&mocked_deps_mut.storage, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<_>>>(),
+            Ok(vec![ReplyCode::PendingPassThrough as u64 + 1]),
+            "Should only keep the other pass-through pending"
+        );
+    }
 
-            self.handle_query(&request)
+    #[test]
+    fn test_pass_through_returning_data() {
+        // Arrange
+        let mut mocked_deps_mut = mock_deps(NumTokensResponse { count: 3 });
+        let mocked_env = testing::mock_env();
+        let deployer = Addr::unchecked("deployer");
+        let mocked_msg_info = testing::mock_info(&deployer.to_string(), &[]);
+        let silver = Coin {
+            amount: Uint128::from(10u16),
+            denom: "silver".to_owned(),
+        };
+        let instantiate_msg = InstantiateMsg {
+            payment_params: payment_params(deployer.to_owned(), vec![silver.to_owned()]),
+            owner: None,
+        };
+        let _ = super::instantiate(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            mocked_msg_info,
+            instantiate_msg,
+        )
+        .expect("Failed to instantiate manager");
+        let _ = super::sudo(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            SudoMsg::RegisterCollection {
+                collection: "collection".to_owned(),
+                label: "my names".to_owned(),
+            },
+        )
+        .expect("Failed to register collection");
+        let execute_msg = ExecuteMsg::PassThrough {
+            collection: "collection".to_owned(),
+            message: CollectionExecuteMsg::Mint {
+                token_id: "alice".to_owned(),
+                owner: "owner".to_owned(),
+                token_uri: None,
+                extension: None,
+            },
+            options: Some(PassThroughOptions {
+                allowlist: None,
+                years: None,
+                catch_errors: false,
+                return_data: Some(DataFormat::Envelope),
+            }),
+        };
+        let num_tokens = to_json_binary(&NameServiceExecuteMsgResponse { num_tokens: 4 })
+            .expect("Failed to serialize counter");
+        let mut prefixed_num_tokens = vec![10, 16];
+        prefixed_num_tokens.extend_from_slice(num_tokens.as_slice());
+
+        // Act
+        let contract_result = super::execute(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            testing::mock_info("executer", &[silver]),
+            execute_msg,
+        );
+        let reply_result = super::reply(
+            mocked_deps_mut.as_mut(),
+            mocked_env,
+            Reply {
+                id: ReplyCode::PendingPassThrough as u64,
+                result: SubMsgResult::Ok(SubMsgResponse {
+                    data: Some(Binary::from(prefixed_num_tokens)),
+                    events: vec![],
+                }),
+            },
+        );
+
+        // Assert
+        let received_response = contract_result.expect("Failed to pass the mint through");
+        assert_eq!(received_response.data, None);
+        assert_eq!(received_response.messages[1].reply_on, ReplyOn::Success);
+        let reply_response = reply_result.expect("Failed to handle the reply");
+        assert_eq!(
+            from_json::<PassThroughData>(reply_response.data.expect("Should return the data"))
+                .expect("Failed to parse the envelope"),
+            PassThroughData {
+                collection: Addr::unchecked("collection"),
+                token_count_before: 3,
+                token_count_after: Some(4),
+                data: Some(num_tokens),
+            }
+        );
+        assert!(PENDING_PASS_THROUGHS.is_empty(&mocked_deps_mut.storage));
+    }
+
+    fn allowlist_leaf(address: &str, allocation: u64) -> [u8; 32] {
+        Sha256::digest(format!("{}:{}", address, allocation).as_bytes()).into()
+    }
+
+    fn allowlist_parent(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+        let (left, right) = if left <= right {
+            (left, right)
+        } else {
+            (right, left)
+        };
+        Sha256::new()
+            .chain_update(left)
+            .chain_update(right)
+            .finalize()
+            .into()
+    }
+
+    #[test]
+    fn test_allowlisted_mint_pass_through() {
+        // Arrange
+        let mut mocked_deps_mut = mock_deps(NumTokensResponse { count: 3 });
+        let mocked_env = testing::mock_env();
+        let deployer = Addr::unchecked("deployer");
+        let mocked_msg_info = testing::mock_info(&deployer.to_string(), &[]);
+        let executer_leaf = allowlist_leaf("executer", 2);
+        let other_leaf = allowlist_leaf("other", 1);
+        let instantiate_msg = InstantiateMsg {
+            payment_params: PaymentParams {
+                phases: vec![MintPhase {
+                    name: "presale".to_owned(),
+                    start: Scheduled::AtHeight(mocked_env.block.height),
+                    end: Expiration::Never {},
+                    mint_prices: vec![],
+                    cap: None,
+                    max_per_wallet: None,
+                    allowlist_root: Some(HexBinary::from(
+                        allowlist_parent(&executer_leaf, &other_leaf).as_slice(),
+                    )),
+                }],
+                ..payment_params(deployer.to_owned(), vec![])
+            },
+            owner: None,
+        };
+        let _ = super::instantiate(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            mocked_msg_info,
+            instantiate_msg,
+        )
+        .expect("Failed to instantiate manager");
+        let _ = super::sudo(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            SudoMsg::RegisterCollection {
+                collection: "collection".to_owned(),
+                label: "my names".to_owned(),
+            },
+        )
+        .expect("Failed to register collection");
+        let mocked_msg_info = testing::mock_info("executer", &[]);
+        let mint_msg = |allocation: Option<u64>| ExecuteMsg::PassThrough {
+            collection: "collection".to_owned(),
+            message: CollectionExecuteMsg::Mint {
+                token_id: "alice".to_owned(),
+                owner: "owner".to_owned(),
+                token_uri: None,
+                extension: None,
+            },
+            options: Some(PassThroughOptions {
+                allowlist: allocation.map(|allocation| AllowlistProof {
+                    allocation,
+                    proof: vec![HexBinary::from(other_leaf.as_slice())],
+                }),
+                years: None,
+                catch_errors: false,
+                return_data: None,
+            }),
+        };
+        let mut mint = |allocation: Option<u64>| {
+            super::execute(
+                mocked_deps_mut.as_mut(),
+                mocked_env.to_owned(),
+                mocked_msg_info.to_owned(),
+                mint_msg(allocation),
+            )
+        };
+
+        // Act
+        let results = [
+            mint(None),
+            mint(Some(3)),
+            mint(Some(2)),
+            mint(Some(2)),
+            mint(Some(2)),
+        ];
+
+        // Assert
+        assert!(
+            matches!(
+                &results[0],
+                Err(ContractError::AllowlistProofRequired { phase }) if phase == "presale"
+            ),
+            "Should have required a proof"
+        );
+        assert!(
+            matches!(&results[1], Err(ContractError::InvalidAllowlistProof)),
+            "Should have rejected an inflated allocation"
+        );
+        assert!(results[2].is_ok(), "Failed to mint with a valid proof");
+        assert!(results[3].is_ok(), "Failed to mint the whole allocation");
+        assert!(
+            matches!(
+                &results[4],
+                Err(ContractError::AllocationUsedUp { allocation: 2, .. })
+            ),
+            "Should have stopped at the allocation"
+        );
+    }
+
+    #[test]
+    fn test_sudo_update_payment_params_rejects_cw20_price_with_phases() {
+        // Arrange
+        let mut mocked_deps_mut = mock_deps(NumTokensResponse { count: 3 });
+        let mocked_env = testing::mock_env();
+        let update_msg = SudoMsg::UpdatePaymentParams(PaymentParams {
+            cw20_mint_price: Some(Cw20CoinVerified {
+                address: Addr::unchecked("token"),
+                amount: Uint128::from(40u16),
+            }),
+            phases: vec![MintPhase {
+                name: "presale".to_owned(),
+                start: Scheduled::AtHeight(mocked_env.block.height),
+                end: Expiration::Never {},
+                mint_prices: vec![],
+                cap: None,
+                max_per_wallet: None,
+                allowlist_root: None,
+            }],
+            ..payment_params(Addr::unchecked("creator"), vec![])
+        });
+
+        // Act
+        let contract_result = super::sudo(mocked_deps_mut.as_mut(), mocked_env, update_msg);
+
+        // Assert
+        assert!(
+            matches!(contract_result, Err(ContractError::Cw20PriceNotFixed)),
+            "Should have rejected a CW20 price during phases"
+        );
+    }
+
+    #[test]
+    fn test_sudo_update_payment_params_rejects_cw20_price_with_auction() {
+        // Arrange
+        let mut mocked_deps_mut = mock_deps(NumTokensResponse { count: 3 });
+        let mocked_env = testing::mock_env();
+        let update_msg = SudoMsg::UpdatePaymentParams(PaymentParams {
+            cw20_mint_price: Some(Cw20CoinVerified {
+                address: Addr::unchecked("token"),
+                amount: Uint128::from(40u16),
+            }),
+            pricing: PricingRule::DutchAuction(DutchAuction {
+                start_price: Coin {
+                    amount: Uint128::from(100u16),
+                    denom: "silver".to_owned(),
+                },
+                floor_amount: Uint128::from(40u16),
+                start_time: mocked_env.block.time,
+                decay_step: Uint128::from(20u16),
+                decay_interval: 60,
+                rebate: false,
+            }),
+            ..payment_params(Addr::unchecked("creator"), vec![])
+        });
+
+        // Act
+        let contract_result = super::sudo(mocked_deps_mut.as_mut(), mocked_env, update_msg);
+
+        // Assert
+        assert!(
+            matches!(contract_result, Err(ContractError::Cw20PriceNotFixed)),
+            "Should have rejected a CW20 price during an auction"
+        );
+    }
+
+    #[test]
+    fn test_mint_pass_through_limits() {
+        // Arrange
+        let mut mocked_deps_mut = mock_deps(NumTokensResponse { count: 3 });
+        let mocked_env = testing::mock_env();
+        let deployer = Addr::unchecked("deployer");
+        let mocked_msg_info = testing::mock_info(&deployer.to_string(), &[]);
+        let instantiate_msg = InstantiateMsg {
+            payment_params: payment_params(deployer.to_owned(), vec![]),
+            owner: None,
+        };
+        let _ = super::instantiate(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            mocked_msg_info.to_owned(),
+            instantiate_msg,
+        )
+        .expect("Failed to instantiate manager");
+        let _ = super::sudo(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            SudoMsg::RegisterCollection {
+                collection: "collection".to_owned(),
+                label: "my names".to_owned(),
+            },
+        )
+        .expect("Failed to register collection");
+        let set_limits = |max_supply: u64| ExecuteMsg::SetMintLimits {
+            collection: "collection".to_owned(),
+            limits: MintLimits {
+                max_per_wallet: Some(1),
+                max_supply: Some(max_supply),
+            },
+        };
+        let _ = super::execute(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            mocked_msg_info.to_owned(),
+            set_limits(10),
+        )
+        .expect("Failed to set mint limits");
+        let mint_msg = ExecuteMsg::PassThrough {
+            collection: "collection".to_owned(),
+            message: CollectionExecuteMsg::Mint {
+                token_id: "alice".to_owned(),
+                owner: "owner".to_owned(),
+                token_uri: None,
+                extension: None,
+            },
+            options: None,
+        };
+        let executer_info = testing::mock_info("executer", &[]);
+        let _ = super::execute(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            executer_info.to_owned(),
+            mint_msg.to_owned(),
+        )
+        .expect("Failed to mint within limits");
+
+        // Act
+        let wallet_result = super::execute(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            executer_info,
+            mint_msg.to_owned(),
+        );
+        let _ = super::execute(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            mocked_msg_info,
+            set_limits(3),
+        )
+        .expect("Failed to lower max supply");
+        let supply_result = super::execute(
+            mocked_deps_mut.as_mut(),
+            mocked_env,
+            testing::mock_info("other", &[]),
+            mint_msg,
+        );
+
+        // Assert
+        assert!(
+            matches!(
+                wallet_result,
+                Err(ContractError::WalletLimitReached { limit: 1 })
+            ),
+            "Should have stopped at the wallet limit"
+        );
+        assert!(
+            matches!(
+                supply_result,
+                Err(ContractError::MaxSupplyReached { max_supply: 3 })
+            ),
+            "Should have counted the collection's own tokens against the max supply"
+        );
+    }
+
+    #[test]
+    fn test_pass_through_unregistered_collection() {
+        // Arrange
+        let mut mocked_deps_mut = mock_deps(NumTokensResponse { count: 3 });
+        let mocked_env = testing::mock_env();
+        let deployer = Addr::unchecked("deployer");
+        let mocked_msg_info = testing::mock_info(&deployer.to_string(), &[]);
+        let instantiate_msg = InstantiateMsg {
+            payment_params: payment_params(deployer.to_owned(), vec![]),
+            owner: None,
+        };
+        let _ = super::instantiate(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            mocked_msg_info.to_owned(),
+            instantiate_msg,
+        )
+        .expect("Failed to instantiate manager");
+        let execute_msg = ExecuteMsg::PassThrough {
+            collection: "collection".to_owned(),
+            message: CollectionExecuteMsg::Mint {
+                token_id: "alice".to_owned(),
+                owner: "owner".to_owned(),
+                token_uri: None,
+                extension: None,
+            },
+            options: None,
+        };
+
+        // Act
+        let contract_result = super::execute(
+            mocked_deps_mut.as_mut(),
+            mocked_env,
+            mocked_msg_info,
+            execute_msg,
+        );
+
+        // Assert
+        assert!(
+            matches!(
+                contract_result,
+                Err(ContractError::CollectionNotRegistered { collection }) if collection == "collection"
+            ),
+            "Should have rejected the unregistered collection"
+        );
+    }
+
+    #[test]
+    fn test_paused_mint_pass_through_until_height() {
+        // Arrange
+        let mut mocked_deps_mut = mock_deps(NumTokensResponse { count: 3 });
+        let mocked_env = testing::mock_env();
+        let deployer = Addr::unchecked("deployer");
+        let mocked_msg_info = testing::mock_info(&deployer.to_string(), &[]);
+        let instantiate_msg = InstantiateMsg {
+            payment_params: payment_params(deployer.to_owned(), vec![]),
+            owner: None,
+        };
+        let _ = super::instantiate(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            mocked_msg_info.to_owned(),
+            instantiate_msg,
+        )
+        .expect("Failed to instantiate manager");
+        for sudo_msg in [
+            SudoMsg::RegisterCollection {
+                collection: "collection".to_owned(),
+                label: "my names".to_owned(),
+            },
+            SudoMsg::Pause {
+                collection: Some("collection".to_owned()),
+                until_height: Some(mocked_env.block.height + 10),
+            },
+        ] {
+            let _ = super::sudo(mocked_deps_mut.as_mut(), mocked_env.to_owned(), sudo_msg)
+                .expect("Failed to configure manager");
         }
+        let execute_msg = ExecuteMsg::PassThrough {
+            collection: "collection".to_owned(),
+            message: CollectionExecuteMsg::Mint {
+                token_id: "alice".to_owned(),
+                owner: "owner".to_owned(),
+                token_uri: None,
+                extension: None,
+            },
+            options: None,
+        };
+        let mut later_env = mocked_env.to_owned();
+        later_env.block.height += 10;
+
+        // Act
+        let paused_result = super::execute(
+            mocked_deps_mut.as_mut(),
+            mocked_env,
+            mocked_msg_info.to_owned(),
+            execute_msg.to_owned(),
+        );
+        let later_result = super::execute(
+            mocked_deps_mut.as_mut(),
+            later_env,
+            mocked_msg_info,
+            execute_msg,
+        );
+
+        // Assert
+        assert!(
+            matches!(
+                paused_result,
+                Err(ContractError::Paused { collection }) if collection == "collection"
+            ),
+            "Should have rejected the mint while paused"
+        );
+        assert!(later_result.is_ok(), "Pause should have expired");
     }
 
-    impl NumTokensMockQuerier {
-        pub fn handle_query(&self, request: &QueryRequest<Empty>) -> QuerierResult {
-            match &request {
-                QueryRequest::Wasm(WasmQuery::Smart {
-                    contract_addr: _,
-                    msg,
-                }) => {
-                    let expected = to_json_binary(&CollectionQueryMsg::NumTokens {})
-                        .expect("Failed to create expected query");
-                    assert_eq!(expected.to_vec(), msg.to_vec(), "Query is not num tokens");
-                    SystemResult::Ok(ContractResult::Ok(
-                        to_json_binary(&self.response)
-                            .expect("Failed to serialize num tokens response"),
-                    ))
-                }
-                _ => self.base.handle_query(request),
-            }
+    #[test]
+    fn test_role_gated_mint_pass_through_requires_minter() {
+        // Arrange
+        let mut mocked_deps_mut = mock_deps(NumTokensResponse { count: 3 });
+        let mocked_env = testing::mock_env();
+        let deployer = Addr::unchecked("deployer");
+        let minter = Addr::unchecked("minter");
+        let instantiate_msg = InstantiateMsg {
+            payment_params: payment_params(deployer.to_owned(), vec![]),
+            owner: None,
+        };
+        let _ = super::instantiate(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            testing::mock_info(deployer.as_ref(), &[]),
+            instantiate_msg,
+        )
+        .expect("Failed to instantiate manager");
+        let _ = super::sudo(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            SudoMsg::RegisterCollection {
+                collection: "collection".to_owned(),
+                label: "my names".to_owned(),
+            },
+        )
+        .expect("Failed to register collection");
+        for execute_msg in [
+            ExecuteMsg::SetPassThroughPolicy {
+                kind: CollectionMsgKind::Mint,
+                policy: PassThroughPolicy::Role(Role::Minter),
+            },
+            ExecuteMsg::GrantRole {
+                role: Role::Minter,
+                member: minter.to_string(),
+            },
+        ] {
+            let _ = super::execute(
+                mocked_deps_mut.as_mut(),
+                mocked_env.to_owned(),
+                testing::mock_info(deployer.as_ref(), &[]),
+                execute_msg,
+            )
+            .expect("Failed to configure minting");
         }
+        let execute_msg = ExecuteMsg::PassThrough {
+            collection: "collection".to_owned(),
+            message: CollectionExecuteMsg::Mint {
+                token_id: "alice".to_owned(),
+                owner: "owner".to_owned(),
+                token_uri: None,
+                extension: None,
+            },
+            options: None,
+        };
+        let not_minter_result = super::execute(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            testing::mock_info("executer", &[]),
+            execute_msg.to_owned(),
+        );
+        assert!(
+            matches!(
+                not_minter_result,
+                Err(ContractError::MissingRole { sender, role: Role::Minter }) if sender == "executer"
+            ),
+            "Should have rejected the non-minter"
+        );
 
-        pub fn new(base: MockQuerier<Empty>, response: NumTokensResponse) -> Self {
-            NumTokensMockQuerier { base, response }
-        }
+        // Act
+        let contract_result = super::execute(
+            mocked_deps_mut.as_mut(),
+            mocked_env,
+            testing::mock_info(minter.as_ref(), &[]),
+            execute_msg,
+        );
+
+        // Assert
+        assert!(contract_result.is_ok(), "Failed to pass message through");
     }
 
     #[test]
-    fn test_instantiate() {
+    fn test_pass_through_forbidden_by_default() {
         // Arrange
         let mut mocked_deps_mut = mock_deps(NumTokensResponse { count: 3 });
         let mocked_env = testing::mock_env();
         let deployer = Addr::unchecked("deployer");
-        let mocked_msg_info = testing::mock_info(deployer.as_ref(), &[]);
-        let payment_params = PaymentParams {
-            beneficiary: deployer.to_owned(),
-            mint_price: None,
+        let instantiate_msg = InstantiateMsg {
+            payment_params: payment_params(deployer.to_owned(), vec![]),
+            owner: None,
+        };
+        let _ = super::instantiate(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            testing::mock_info(deployer.as_ref(), &[]),
+            instantiate_msg,
+        )
+        .expect("Failed to instantiate manager");
+        let _ = super::sudo(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            SudoMsg::RegisterCollection {
+                collection: "collection".to_owned(),
+                label: "my names".to_owned(),
+            },
+        )
+        .expect("Failed to register collection");
+        let execute_msg = ExecuteMsg::PassThrough {
+            collection: "collection".to_owned(),
+            // Built from JSON because cw721 pins its own cw-ownable `Action`.
+            message: from_json(
+                r#"{"update_minter_ownership":{"transfer_ownership":{"new_owner":"attacker"}}}"#,
+            )
+            .expect("Failed to deserialize ownership transfer"),
+            options: None,
+        };
+
+        // Act
+        let contract_result = super::execute(
+            mocked_deps_mut.as_mut(),
+            mocked_env,
+            testing::mock_info(deployer.as_ref(), &[]),
+            execute_msg,
+        );
+
+        // Assert
+        assert!(
+            matches!(
+                contract_result,
+                Err(ContractError::PassThroughForbidden {
+                    kind: CollectionMsgKind::UpdateMinterOwnership
+                })
+            ),
+            "Should have forbidden the ownership transfer, even to the owner"
+        );
+    }
+
+    #[test]
+    fn test_reply_pass_through() {
+        // Arrange
+        let mut mocked_deps_mut = mock_deps(NumTokensResponse { count: 3 });
+        let mocked_env = testing::mock_env();
+        let num_tokens = to_json_binary(&NameServiceExecuteMsgResponse { num_tokens: 4 })
+            .expect("Failed to serialize counter");
+        let mut prefixed_num_tokens = vec![10, 16];
+        prefixed_num_tokens.extend_from_slice(&num_tokens.as_slice());
+        let reply = Reply {
+            id: ReplyCode::PassThrough as u64,
+            result: SubMsgResult::Ok(SubMsgResponse {
+                data: Some(Binary::from(prefixed_num_tokens)),
+                events: vec![],
+            }),
+        };
+
+        // Act
+        let contract_result = super::reply(mocked_deps_mut.as_mut(), mocked_env, reply);
+
+        // Assert
+        assert!(contract_result.is_ok(), "Failed to pass reply through");
+        let received_response = contract_result.unwrap();
+        let expected_response = Response::default()
+            .add_event(Event::new("my-collection-manager").add_attribute("token-count-after", "4"));
+        assert_eq!(received_response, expected_response);
+    }
+
+    #[test]
+    fn test_reply_pass_through_decoding() {
+        // Arrange
+        let mut mocked_deps_mut = mock_deps(NumTokensResponse { count: 3 });
+        let mocked_env = testing::mock_env();
+        // Padded past 127 bytes, so that its length takes two bytes of varint.
+        let long_num_tokens = format!("{{\"num_tokens\":5{}}}", " ".repeat(150));
+        let mut wrapped_long_num_tokens = vec![10, 0xa6, 0x01];
+        wrapped_long_num_tokens.extend_from_slice(long_num_tokens.as_bytes());
+        assert_eq!(long_num_tokens.len(), 166);
+        let raw_num_tokens = to_json_binary(&NameServiceExecuteMsgResponse { num_tokens: 6 })
+            .expect("Failed to serialize counter");
+        let reply = |data: Vec<u8>| Reply {
+            id: ReplyCode::PassThrough as u64,
+            result: SubMsgResult::Ok(SubMsgResponse {
+                data: Some(Binary::from(data)),
+                events: vec![],
+            }),
+        };
+
+        // Act
+        let long_result = super::reply(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            reply(wrapped_long_num_tokens),
+        );
+        let raw_result = super::reply(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            reply(raw_num_tokens.to_vec()),
+        );
+        let undecodable_result = super::reply(
+            mocked_deps_mut.as_mut(),
+            mocked_env,
+            reply(b"done".to_vec()),
+        );
+
+        // Assert
+        let token_count_event = |num_tokens: &str| {
+            Event::new("my-collection-manager").add_attribute("token-count-after", num_tokens)
+        };
+        assert_eq!(
+            long_result.expect("Failed to decode the long data").events,
+            vec![token_count_event("5")]
+        );
+        assert_eq!(
+            raw_result.expect("Failed to decode the raw data").events,
+            vec![token_count_event("6")]
+        );
+        let undecodable_events = undecodable_result
+            .expect("Should not fail on undecodable data")
+            .events;
+        assert_eq!(undecodable_events.len(), 1);
+        assert_eq!(
+            undecodable_events[0].attributes[0].key,
+            "token-count-decode-error"
+        );
+    }
+
+    #[test]
+    fn test_create_collection() {
+        // Arrange
+        let mut mocked_deps_mut = testing::mock_dependencies();
+        let mocked_env = testing::mock_env();
+        let mocked_msg_info = testing::mock_info("creator", &[]);
+        cw_ownable::initialize_owner(
+            &mut mocked_deps_mut.storage,
+            &mocked_deps_mut.api,
+            Some("creator"),
+        )
+        .expect("Failed to initialize owner");
+        let execute_msg = ExecuteMsg::CreateCollection {
+            code_id: 7,
+            label: "my names".to_owned(),
+            name: "my names".to_owned(),
+            symbol: "MYN".to_owned(),
+            creator: None,
+            withdraw_address: None,
+            salt: None,
+        };
+
+        // Act
+        let contract_result = super::execute(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            mocked_msg_info,
+            execute_msg,
+        );
+
+        // Assert
+        assert!(contract_result.is_ok(), "Failed to create collection");
+        let received_response = contract_result.unwrap();
+        let expected_instantiate_msg = CollectionInstantiateMsg {
+            name: "my names".to_owned(),
+            symbol: "MYN".to_owned(),
+            collection_info_extension: None,
+            minter: Some(mocked_env.contract.address.to_string()),
+            creator: None,
+            withdraw_address: None,
+        };
+        let expected_response = Response::default().add_submessage(SubMsg {
+            id: ReplyCode::CreateCollection as u64,
+            msg: CosmosMsg::<Empty>::Wasm(WasmMsg::Instantiate {
+                admin: None,
+                code_id: 7,
+                msg: to_json_binary(&expected_instantiate_msg)
+                    .expect("Failed to serialize instantiate message"),
+                funds: vec![],
+                label: "my names".to_owned(),
+            }),
+            reply_on: ReplyOn::Success,
+            gas_limit: None,
+        });
+        assert_eq!(received_response, expected_response);
+        assert_eq!(
+            PENDING_COLLECTION_LABEL
+                .load(&mocked_deps_mut.storage)
+                .expect("Failed to load pending label"),
+            "my names"
+        );
+    }
+
+    #[test]
+    fn test_create_collection_requires_owner() {
+        // Arrange
+        let mut mocked_deps_mut = testing::mock_dependencies();
+        let mocked_env = testing::mock_env();
+        cw_ownable::initialize_owner(
+            &mut mocked_deps_mut.storage,
+            &mocked_deps_mut.api,
+            Some("creator"),
+        )
+        .expect("Failed to initialize owner");
+        let execute_msg = ExecuteMsg::CreateCollection {
+            code_id: 7,
+            label: "my names".to_owned(),
+            name: "my names".to_owned(),
+            symbol: "MYN".to_owned(),
+            creator: None,
+            withdraw_address: None,
+            salt: None,
+        };
+
+        // Act
+        let contract_result = super::execute(
+            mocked_deps_mut.as_mut(),
+            mocked_env,
+            testing::mock_info("stranger", &[]),
+            execute_msg,
+        );
+
+        // Assert
+        assert!(
+            matches!(
+                contract_result,
+                Err(ContractError::Ownership(OwnershipError::NotOwner))
+            ),
+            "Should have rejected the non-owner"
+        );
+        assert!(
+            PENDING_COLLECTION_LABEL
+                .may_load(&mocked_deps_mut.storage)
+                .expect("Failed to load pending label")
+                .is_none(),
+            "Should not have saved a pending label"
+        );
+    }
+
+    #[test]
+    fn test_create_collection_with_salt() {
+        // Arrange
+        let mut mocked_deps_mut = testing::mock_dependencies();
+        let mocked_env = testing::mock_env();
+        let mocked_msg_info = testing::mock_info("creator", &[]);
+        cw_ownable::initialize_owner(
+            &mut mocked_deps_mut.storage,
+            &mocked_deps_mut.api,
+            Some("creator"),
+        )
+        .expect("Failed to initialize owner");
+        let salt = Binary::from(b"my-names".to_vec());
+        let execute_msg = ExecuteMsg::CreateCollection {
+            code_id: 7,
+            label: "my names".to_owned(),
+            name: "my names".to_owned(),
+            symbol: "MYN".to_owned(),
+            creator: None,
+            withdraw_address: None,
+            salt: Some(salt.to_owned()),
+        };
+
+        // Act
+        let contract_result = super::execute(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            mocked_msg_info,
+            execute_msg,
+        );
+
+        // Assert
+        assert!(contract_result.is_ok(), "Failed to create collection");
+        let received_response = contract_result.unwrap();
+        let expected_instantiate_msg = CollectionInstantiateMsg {
+            name: "my names".to_owned(),
+            symbol: "MYN".to_owned(),
+            collection_info_extension: None,
+            minter: Some(mocked_env.contract.address.to_string()),
+            creator: None,
+            withdraw_address: None,
+        };
+        let expected_response = Response::default().add_submessage(SubMsg {
+            id: ReplyCode::CreateCollection as u64,
+            msg: CosmosMsg::<Empty>::Wasm(WasmMsg::Instantiate2 {
+                admin: None,
+                code_id: 7,
+                label: "my names".to_owned(),
+                msg: to_json_binary(&expected_instantiate_msg)
+                    .expect("Failed to serialize instantiate message"),
+                funds: vec![],
+                salt,
+            }),
+            reply_on: ReplyOn::Success,
+            gas_limit: None,
+        });
+        assert_eq!(received_response, expected_response);
+    }
+
+    #[test]
+    fn test_reply_create_collection() {
+        // Arrange
+        let mut mocked_deps_mut = testing::mock_dependencies();
+        let mocked_env = testing::mock_env();
+        let collection = Addr::unchecked("new-collection");
+        PENDING_COLLECTION_LABEL
+            .save(&mut mocked_deps_mut.storage, &"my names".to_owned())
+            .expect("Failed to save pending label");
+        let mut instantiate_data = vec![10, collection.as_str().len() as u8];
+        instantiate_data.extend_from_slice(collection.as_bytes());
+        let reply = Reply {
+            id: ReplyCode::CreateCollection as u64,
+            result: SubMsgResult::Ok(SubMsgResponse {
+                data: Some(Binary::from(instantiate_data)),
+                events: vec![],
+            }),
         };
+
+        // Act
+        let contract_result = super::reply(mocked_deps_mut.as_mut(), mocked_env.to_owned(), reply);
+
+        // Assert
+        assert!(
+            contract_result.is_ok(),
+            "Failed to handle instantiate reply"
+        );
+        let received_response = contract_result.unwrap();
+        let expected_response = Response::default().add_event(
+            Event::new("my-collection-manager")
+                .add_attribute("create-collection", collection.to_owned()),
+        );
+        assert_eq!(received_response, expected_response);
+        assert_eq!(
+            COLLECTIONS
+                .load(&mocked_deps_mut.storage, &collection)
+                .expect("Failed to load collection record"),
+            CollectionRecord {
+                label: "my names".to_owned(),
+                registered_at_height: mocked_env.block.height,
+            }
+        );
+        assert!(
+            PENDING_COLLECTION_LABEL
+                .may_load(&mocked_deps_mut.storage)
+                .unwrap()
+                .is_none(),
+            "Pending label should have been cleared"
+        );
+    }
+
+    #[test]
+    fn test_update_payment_params_requires_owner() {
+        // Arrange
+        let mut mocked_deps_mut = testing::mock_dependencies();
+        let mocked_env = testing::mock_env();
+        let deployer = Addr::unchecked("deployer");
+        let owner = Addr::unchecked("owner");
         let instantiate_msg = InstantiateMsg {
-            payment_params: payment_params.to_owned(),
+            payment_params: payment_params(deployer.to_owned(), vec![]),
+            owner: Some(owner.to_string()),
         };
+        let _ = super::instantiate(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            testing::mock_info(deployer.as_ref(), &[]),
+            instantiate_msg,
+        )
+        .expect("Failed to instantiate manager");
+        let new_payment_params = payment_params(
+            owner.to_owned(),
+            vec![Coin {
+                denom: "silver".to_owned(),
+                amount: Uint128::one(),
+            }],
+        );
+        let execute_msg = ExecuteMsg::UpdatePaymentParams(new_payment_params.to_owned());
+        let not_owner_result = super::execute(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            testing::mock_info(deployer.as_ref(), &[]),
+            execute_msg.to_owned(),
+        );
+        assert!(
+            matches!(
+                not_owner_result,
+                Err(ContractError::MissingRole { sender, role: Role::PaymentManager })
+                    if sender == deployer.as_str()
+            ),
+            "Should have rejected the non-owner"
+        );
 
         // Act
-        let result = super::instantiate(
+        let contract_result = super::execute(
             mocked_deps_mut.as_mut(),
-            mocked_env.to_owned(),
-            mocked_msg_info,
-            instantiate_msg.to_owned(),
+            mocked_env,
+            testing::mock_info(owner.as_ref(), &[]),
+            execute_msg,
         );
 
         // Assert
-        assert!(result.is_ok(), "Failed to instantiate manager");
-        let received_response = result.unwrap();
+        assert!(contract_result.is_ok(), "Failed to update payment params");
+        let received_response = contract_result.unwrap();
         let expected_response = Response::default().add_event(
             Event::new("my-collection-manager")
-                .add_attribute("update-contract-version", "0.1.0")
-                .add_attribute("update-payment-params-beneficiary", deployer)
-                .add_attribute("update-payment-params-mint-price", "none"),
+                .add_attribute("update-payment-params-beneficiary", owner)
+                .add_attribute("update-payment-params-beneficiary-share-bps", "10000")
+                .add_attribute("update-payment-params-mint-price-denom", "silver")
+                .add_attribute("update-payment-params-mint-price-amount", "1"),
         );
         assert_eq!(received_response, expected_response);
-        let saved_payment_params = PAYMENT_PARAMS
+        let payment_params = PAYMENT_PARAMS
             .load(&mocked_deps_mut.storage)
             .expect("Failed to load payment params");
-        assert_eq!(saved_payment_params, payment_params);
-        assert_contract_version(&mocked_deps_mut.storage, "my-collection-manager", "0.1.0")
-            .expect("Failed to assert contract version");
-        let contract_info = cw2::CONTRACT
-            .load(&mocked_deps_mut.storage)
-            .expect("Failed to load contract info");
-        assert_eq!(
-            contract_info,
-            ContractVersion {
-                contract: "my-collection-manager".to_owned(),
-                version: "0.1.0".to_owned(),
-            }
-        );
+        assert_eq!(payment_params, new_payment_params);
     }
 
     #[test]
-    fn test_pass_through() {
+    fn test_grant_role() {
         // Arrange
-        let mut mocked_deps_mut = mock_deps(NumTokensResponse { count: 3 });
+        let mut mocked_deps_mut = testing::mock_dependencies();
         let mocked_env = testing::mock_env();
         let deployer = Addr::unchecked("deployer");
-        let mocked_msg_info = testing::mock_info(&deployer.to_string(), &[]);
-        let instantiate_msg = InstantiateMsg {
-            payment_params: PaymentParams {
-                beneficiary: deployer.to_owned(),
-                mint_price: None,
-            },
+        let treasurer = Addr::unchecked("treasurer");
+        cw_ownable::initialize_owner(
+            &mut mocked_deps_mut.storage,
+            &mocked_deps_mut.api,
+            Some(deployer.as_str()),
+        )
+        .expect("Failed to initialize owner");
+        let execute_msg = ExecuteMsg::GrantRole {
+            role: Role::Treasurer,
+            member: treasurer.to_string(),
         };
-        let _ = super::instantiate(
+        let not_owner_result = super::execute(
             mocked_deps_mut.as_mut(),
             mocked_env.to_owned(),
-            mocked_msg_info,
-            instantiate_msg,
-        )
-        .expect("Failed to instantiate manager");
-        let executer = Addr::unchecked("executer");
-        let fund_sent = Coin {
-            denom: "gold".to_owned(),
-            amount: Uint128::from(335u128),
-        };
-        let mocked_msg_info = testing::mock_info(&executer.to_string(), &[fund_sent.to_owned()]);
-        let name = "alice".to_owned();
-        let owner = Addr::unchecked("owner");
-        let inner_msg = CollectionExecuteMsg::Mint {
-            token_id: name.to_owned(),
-            owner: owner.to_string(),
-            token_uri: None,
-            extension: None,
-        };
-        let execute_msg = ExecuteMsg::PassThrough {
-            collection: "collection".to_owned(),
-            message: inner_msg.to_owned(),
-        };
+            testing::mock_info(treasurer.as_ref(), &[]),
+            execute_msg.to_owned(),
+        );
+        assert!(
+            not_owner_result.is_err(),
+            "Should have rejected the non-owner"
+        );
 
         // Act
         let contract_result = super::execute(
             mocked_deps_mut.as_mut(),
             mocked_env,
-            mocked_msg_info,
+            testing::mock_info(deployer.as_ref(), &[]),
             execute_msg,
         );
 
         // Assert
-        assert!(contract_result.is_ok(), "Failed to pass message through");
+        assert!(contract_result.is_ok(), "Failed to grant role");
         let received_response = contract_result.unwrap();
-        let expected_response = Response::default()
-            .add_message(BankMsg::Send {
-                to_address: executer.to_string(),
-                amount: vec![fund_sent],
-            })
-            .add_submessage(SubMsg {
-                id: ReplyCode::PassThrough as u64,
-                msg: CosmosMsg::<Empty>::Wasm(WasmMsg::Execute {
-                    contract_addr: "collection".to_owned(),
-                    msg: to_json_binary(&inner_msg).expect("Failed to serialize inner message"),
-                    funds: vec![],
-                }),
-                reply_on: ReplyOn::Success,
-                gas_limit: None,
-            })
-            .add_event(
-                Event::new("my-collection-manager").add_attribute("token-count-before", "3"),
-            );
+        let expected_response = Response::default().add_event(
+            Event::new("my-collection-manager")
+                .add_attribute("grant-role", "treasurer")
+                .add_attribute("grant-role-member", treasurer.to_owned()),
+        );
         assert_eq!(received_response, expected_response);
+        assert!(
+            ROLE_MEMBERS.has(&mocked_deps_mut.storage, ("treasurer", &treasurer)),
+            "Role should have been saved"
+        );
     }
 
     #[test]
-    fn test_paid_mint_pass_through() {
+    fn test_sudo_update_payment_params() {
         // Arrange
-        let mut mocked_deps_mut = mock_deps(NumTokensResponse { count: 3 });
+        let mut mocked_deps_mut = testing::mock_dependencies();
+        let mocked_env = testing::mock_env();
+        let beneficiary = Addr::unchecked("beneficiary");
+        let new_payment_params = payment_params(
+            beneficiary.to_owned(),
+            vec![Coin {
+                denom: "silver".to_owned(),
+                amount: Uint128::one(),
+            }],
+        );
+        let sudo_msg = SudoMsg::UpdatePaymentParams(new_payment_params.to_owned());
+
+        // Act
+        let contract_result = super::sudo(mocked_deps_mut.as_mut(), mocked_env, sudo_msg);
+
+        // Assert
+        assert!(contract_result.is_ok(), "Failed to sudo");
+        let received_response = contract_result.unwrap();
+        let expected_response = Response::default().add_event(
+            Event::new("my-collection-manager")
+                .add_attribute("update-payment-params-beneficiary", beneficiary)
+                .add_attribute("update-payment-params-beneficiary-share-bps", "10000")
+                .add_attribute("update-payment-params-mint-price-denom", "silver")
+                .add_attribute("update-payment-params-mint-price-amount", "1"),
+        );
+        assert_eq!(received_response, expected_response);
+        let payment_params = PAYMENT_PARAMS
+            .load(&mocked_deps_mut.storage)
+            .expect("Failed to load payment params");
+        assert_eq!(payment_params, new_payment_params);
+    }
+
+    #[test]
+    fn test_sudo_update_collection_payment_params() {
+        // Arrange
+        let mut mocked_deps_mut = testing::mock_dependencies();
         let mocked_env = testing::mock_env();
         let beneficiary = Addr::unchecked("beneficiary");
+        let collection = Addr::unchecked("collection");
+        let new_payment_params = payment_params(
+            beneficiary.to_owned(),
+            vec![Coin {
+                denom: "silver".to_owned(),
+                amount: Uint128::one(),
+            }],
+        );
+        let sudo_msg = SudoMsg::UpdateCollectionPaymentParams {
+            collection: collection.to_string(),
+            payment_params: new_payment_params.to_owned(),
+        };
+
+        // Act
+        let contract_result = super::sudo(mocked_deps_mut.as_mut(), mocked_env, sudo_msg);
+
+        // Assert
+        assert!(contract_result.is_ok(), "Failed to sudo");
+        let received_response = contract_result.unwrap();
+        let expected_response = Response::default().add_event(
+            Event::new("my-collection-manager")
+                .add_attribute("update-payment-params-collection", collection.to_owned())
+                .add_attribute("update-payment-params-beneficiary", beneficiary)
+                .add_attribute("update-payment-params-beneficiary-share-bps", "10000")
+                .add_attribute("update-payment-params-mint-price-denom", "silver")
+                .add_attribute("update-payment-params-mint-price-amount", "1"),
+        );
+        assert_eq!(received_response, expected_response);
+        let payment_params = COLLECTION_PAYMENT_PARAMS
+            .load(&mocked_deps_mut.storage, &collection)
+            .expect("Failed to load collection payment params");
+        assert_eq!(payment_params, new_payment_params);
+        assert!(
+            PAYMENT_PARAMS
+                .may_load(&mocked_deps_mut.storage)
+                .unwrap()
+                .is_none(),
+            "Global payment params should not have been touched"
+        );
+    }
+
+    #[test]
+    fn test_paid_mint_pass_through_collection_payment_params() {
+        // Arrange
+        let mut mocked_deps_mut = mock_deps(NumTokensResponse { count: 3 });
+        let mocked_env = testing::mock_env();
         let deployer = Addr::unchecked("deployer");
+        let collection_beneficiary = Addr::unchecked("collection-beneficiary");
         let mocked_msg_info = testing::mock_info(&deployer.to_string(), &[]);
-        let minting_price = Coin {
-            amount: Uint128::from(55u16),
-            denom: "silver".to_owned(),
-        };
         let instantiate_msg = InstantiateMsg {
-            payment_params: PaymentParams {
-                beneficiary: beneficiary.to_owned(),
-                mint_price: Some(minting_price.to_owned()),
-            },
+            payment_params: payment_params(deployer.to_owned(), vec![]),
+            owner: None,
         };
         let _ = super::instantiate(
             mocked_deps_mut.as_mut(),
@@ -471,60 +5571,62 @@ mod tests {
             instantiate_msg,
         )
         .expect("Failed to instantiate manager");
-        let executer = Addr::unchecked("executer");
-        let extra_fund_sent = Coin {
-            denom: "gold".to_owned(),
-            amount: Uint128::from(335u128),
-        };
-        let fistful_silver = Coin {
-            amount: Uint128::from(30u16),
+        let _ = super::sudo(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            SudoMsg::RegisterCollection {
+                collection: "collection".to_owned(),
+                label: "my names".to_owned(),
+            },
+        )
+        .expect("Failed to register collection");
+        let minting_price = Coin {
+            amount: Uint128::from(12u16),
             denom: "silver".to_owned(),
         };
-        let mocked_msg_info = testing::mock_info(
-            &executer.to_string(),
-            &[
-                extra_fund_sent.to_owned(),
-                fistful_silver.to_owned(),
-                fistful_silver,
-            ],
-        );
-        let name = "alice".to_owned();
-        let owner = Addr::unchecked("owner");
+        let _ = super::sudo(
+            mocked_deps_mut.as_mut(),
+            mocked_env.to_owned(),
+            SudoMsg::UpdateCollectionPaymentParams {
+                collection: "collection".to_owned(),
+                payment_params: payment_params(
+                    collection_beneficiary.to_owned(),
+                    vec![minting_price.to_owned()],
+                ),
+            },
+        )
+        .expect("Failed to set collection payment params");
+        let executer = Addr::unchecked("executer");
+        let mocked_msg_info =
+            testing::mock_info(&executer.to_string(), &[minting_price.to_owned()]);
         let inner_msg = CollectionExecuteMsg::Mint {
-            token_id: name.to_owned(),
-            owner: owner.to_string(),
+            token_id: "alice".to_owned(),
+            owner: "owner".to_owned(),
             token_uri: None,
             extension: None,
         };
         let execute_msg = ExecuteMsg::PassThrough {
             collection: "collection".to_owned(),
             message: inner_msg.to_owned(),
+            options: None,
         };
 
         // Act
         let contract_result = super::execute(
             mocked_deps_mut.as_mut(),
             mocked_env,
-            mocked_msg_info.to_owned(),
+            mocked_msg_info,
             execute_msg,
         );
 
         // Assert
         assert!(contract_result.is_ok(), "Failed to pass message through");
         let received_response = contract_result.unwrap();
-        let expected_denom_change = Coin {
-            amount: Uint128::from(5u16),
-            denom: "silver".to_owned(),
-        };
         let expected_response = Response::default()
             .add_message(BankMsg::Send {
-                to_address: beneficiary.to_string(),
+                to_address: collection_beneficiary.to_string(),
                 amount: vec![minting_price],
             })
-            .add_message(BankMsg::Send {
-                to_address: mocked_msg_info.sender.to_string(),
-                amount: vec![extra_fund_sent, expected_denom_change],
-            })
             .add_submessage(SubMsg {
                 id: ReplyCode::PassThrough as u64,
                 msg: CosmosMsg::<Empty>::Wasm(WasmMsg::Execute {
@@ -536,71 +5638,47 @@ mod tests {
                 gas_limit: None,
             })
             .add_event(
-                Event::new("my-collection-manager").add_attribute("token-count-before", "3"),
+                Event::new("my-collection-manager")
+                    .add_attribute("token-count-before", "3")
+                    .add_attribute("mint-payment-denom", "silver")
+                    .add_attribute("mint-payment-amount", "12"),
             );
         assert_eq!(received_response, expected_response);
     }
 
     #[test]
-    fn test_reply_pass_through() {
-        // Arrange
-        let mut mocked_deps_mut = mock_deps(NumTokensResponse { count: 3 });
-        let mocked_env = testing::mock_env();
-        let num_tokens = to_json_binary(&NameServiceExecuteMsgResponse { num_tokens: 4 })
-            .expect("Failed to serialize counter");
-        let mut prefixed_num_tokens = vec![10, 16];
-        prefixed_num_tokens.extend_from_slice(&num_tokens.as_slice());
-        let reply = Reply {
-            id: ReplyCode::PassThrough as u64,
-            result: SubMsgResult::Ok(SubMsgResponse {
-                data: Some(Binary::from(prefixed_num_tokens)),
-                events: vec![],
-            }),
-        };
-
-        // Act
-        let contract_result = super::reply(mocked_deps_mut.as_mut(), mocked_env, reply);
-
-        // Assert
-        assert!(contract_result.is_ok(), "Failed to pass reply through");
-        let received_response = contract_result.unwrap();
-        let expected_response = Response::default()
-            .add_event(Event::new("my-collection-manager").add_attribute("token-count-after", "4"));
-        assert_eq!(received_response, expected_response);
-    }
-
-    #[test]
-    fn test_sudo_update_payment_params() {
+    fn test_sudo_register_collection() {
         // Arrange
         let mut mocked_deps_mut = testing::mock_dependencies();
         let mocked_env = testing::mock_env();
-        let beneficiary = Addr::unchecked("beneficiary");
-        let new_payment_params = PaymentParams {
-            beneficiary: beneficiary.to_owned(),
-            mint_price: Some(Coin {
-                denom: "silver".to_owned(),
-                amount: Uint128::one(),
-            }),
+        let collection = Addr::unchecked("collection");
+        let sudo_msg = SudoMsg::RegisterCollection {
+            collection: collection.to_string(),
+            label: "my names".to_owned(),
         };
-        let sudo_msg = SudoMsg::UpdatePaymentParams(new_payment_params.to_owned());
 
         // Act
-        let contract_result = super::sudo(mocked_deps_mut.as_mut(), mocked_env, sudo_msg);
+        let contract_result =
+            super::sudo(mocked_deps_mut.as_mut(), mocked_env.to_owned(), sudo_msg);
 
         // Assert
         assert!(contract_result.is_ok(), "Failed to sudo");
         let received_response = contract_result.unwrap();
         let expected_response = Response::default().add_event(
             Event::new("my-collection-manager")
-                .add_attribute("update-payment-params-beneficiary", beneficiary)
-                .add_attribute("update-payment-params-mint-price-denom", "silver")
-                .add_attribute("update-payment-params-mint-price-amount", "1"),
+                .add_attribute("register-collection", collection.to_owned()),
         );
         assert_eq!(received_response, expected_response);
-        let payment_params = PAYMENT_PARAMS
-            .load(&mocked_deps_mut.storage)
-            .expect("Failed to load payment params");
-        assert_eq!(payment_params, new_payment_params);
+        let record = COLLECTIONS
+            .load(&mocked_deps_mut.storage, &collection)
+            .expect("Failed to load collection record");
+        assert_eq!(
+            record,
+            CollectionRecord {
+                label: "my names".to_owned(),
+                registered_at_height: mocked_env.block.height,
+            }
+        );
     }
 
     #[test]
@@ -609,15 +5687,16 @@ mod tests {
         let mut mocked_deps_mut = testing::mock_dependencies();
         let mocked_env = testing::mock_env();
         let beneficiary = Addr::unchecked("beneficiary");
-        let new_payment_params = PaymentParams {
-            beneficiary: beneficiary.to_owned(),
-            mint_price: Some(Coin {
+        let new_payment_params = payment_params(
+            beneficiary.to_owned(),
+            vec![Coin {
                 denom: "silver".to_owned(),
                 amount: Uint128::one(),
-            }),
-        };
+            }],
+        );
         let migrate_msg = MigrateMsg {
             payment_params: new_payment_params.to_owned(),
+            owner: None,
         };
 
         // Act
@@ -630,8 +5709,10 @@ mod tests {
             Event::new("my-collection-manager")
                 .add_attribute("update-contract-version", "0.1.0")
                 .add_attribute("update-payment-params-beneficiary", beneficiary)
+                .add_attribute("update-payment-params-beneficiary-share-bps", "10000")
                 .add_attribute("update-payment-params-mint-price-denom", "silver")
-                .add_attribute("update-payment-params-mint-price-amount", "1"),
+                .add_attribute("update-payment-params-mint-price-amount", "1")
+                .add_attribute("update-owner", "none"),
         );
         assert_eq!(received_response, expected_response);
         let saved_payment_params = PAYMENT_PARAMS
@@ -651,4 +5732,40 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn test_load_payment_params_with_single_mint_price() {
+        // Arrange
+        let mut mocked_deps_mut = testing::mock_dependencies();
+        let beneficiary = Addr::unchecked("beneficiary");
+        let saved_with_price =
+            br#"{"beneficiary":"beneficiary","mint_price":{"denom":"silver","amount":"55"}}"#;
+        let saved_without_price = br#"{"beneficiary":"beneficiary","mint_price":null}"#;
+
+        // Act
+        mocked_deps_mut
+            .storage
+            .set(PAYMENT_PARAMS.as_slice(), saved_with_price);
+        let loaded_with_price = PAYMENT_PARAMS.load(&mocked_deps_mut.storage);
+        mocked_deps_mut
+            .storage
+            .set(PAYMENT_PARAMS.as_slice(), saved_without_price);
+        let loaded_without_price = PAYMENT_PARAMS.load(&mocked_deps_mut.storage);
+
+        // Assert
+        assert_eq!(
+            loaded_with_price.expect("Failed to load single mint price"),
+            payment_params(
+                beneficiary.to_owned(),
+                vec![Coin {
+                    denom: "silver".to_owned(),
+                    amount: Uint128::from(55u16),
+                }],
+            )
+        );
+        assert_eq!(
+            loaded_without_price.expect("Failed to load missing mint price"),
+            payment_params(beneficiary, vec![])
+        );
+    }
 }
@@ -1,15 +1,141 @@
-use cosmwasm_std::{Coin, StdError};
+use cosmwasm_std::{Coin, Instantiate2AddressError, StdError, Timestamp, Uint128};
 use cw2::VersionError;
+use cw20::Cw20CoinVerified;
+use cw_ownable::OwnershipError;
+use cw_utils::{ParseReplyError, PaymentError};
 use thiserror::Error;
 
+use crate::msg::{CollectionMsgKind, Role};
+
 #[derive(Error, Debug)]
 pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
     #[error("price cannot be zero")]
     ZeroPrice,
-    #[error("missing payment {:?}", missing_payment)]
-    MissingPayment { missing_payment: Coin },
+    #[error("beneficiary shares add up to {total_bps} instead of 10000 basis points")]
+    InvalidShares { total_bps: u32 },
+    #[error("beneficiary {beneficiary} has a zero share")]
+    ZeroShare { beneficiary: String },
+    #[error("beneficiary {beneficiary} is listed more than once")]
+    DuplicateBeneficiary { beneficiary: String },
+    #[error("phase {phase} is listed more than once")]
+    DuplicatePhase { phase: String },
+    #[error(
+        "a CW20 mint price is only accepted with the fixed pricing rule and without mint phases"
+    )]
+    Cw20PriceNotFixed,
+    #[error("no mint phase is active")]
+    NoActivePhase,
+    #[error("mint phase {phase} has reached its cap")]
+    PhaseCapReached { phase: String },
+    #[error("wallet limit of {limit} mints in phase {phase} reached")]
+    PhaseWalletLimitReached { phase: String, limit: u64 },
+    #[error("wallet limit of {limit} mints reached")]
+    WalletLimitReached { limit: u64 },
+    #[error("max supply of {max_supply} reached")]
+    MaxSupplyReached { max_supply: u64 },
+    #[error("mint phase {phase} requires an allowlist proof")]
+    AllowlistProofRequired { phase: String },
+    #[error("invalid allowlist proof")]
+    InvalidAllowlistProof,
+    #[error("allocation of {allocation} in mint phase {phase} is used up")]
+    AllocationUsedUp { phase: String, allocation: u64 },
+    #[error("auction floor of {floor_amount} is above its start price")]
+    FloorAboveStartPrice { floor_amount: Uint128 },
+    #[error("auction price must decay by a non-zero step at a non-zero interval")]
+    ZeroDecay,
+    #[error("bonding curve price must rise with supply")]
+    FlatCurve,
+    #[error("curve pieces must start at supply 0, with increasing supplies and prices")]
+    InvalidCurvePieces,
+    #[error("length tiers must start at length 1, with increasing lengths")]
+    InvalidLengthTiers,
+    #[error("premium name {name} is listed more than once")]
+    DuplicatePremiumName { name: String },
+    #[error("price depends on the name, see QuoteName")]
+    NameRequired,
+    #[error("name lengths must be at least 1, with the minimum not above the maximum")]
+    InvalidNameLengths,
+    #[error("name is shorter than {min_length} characters")]
+    NameTooShort { min_length: u32 },
+    #[error("name is longer than {max_length} characters")]
+    NameTooLong { max_length: u32 },
+    #[error("name cannot start or end with a hyphen")]
+    NameHyphenAtEdge,
+    #[error("name cannot contain {character:?}")]
+    InvalidNameCharacter { character: char },
+    #[error("name must be in NFC, without compatibility characters")]
+    NameNotNormalized,
+    #[error("name cannot mix scripts")]
+    MixedScripts,
+    #[error("name only uses letters that look like Latin ones")]
+    WholeScriptConfusable,
+    #[error("name {name} is reserved")]
+    NameReserved { name: String },
+    #[error("subscription must allow at least one year")]
+    ZeroMaxYears,
+    #[error("auction with a rebate cannot be combined with a subscription")]
+    RebateWithSubscription,
+    #[error("years must be between 1 and {max_years}")]
+    InvalidYears { max_years: u32 },
+    #[error("collection {collection} has no subscription")]
+    NoSubscription { collection: String },
+    #[error("name {name} is not registered with a subscription")]
+    NameNotRegistered { name: String },
+    #[error("name {name} can only be reclaimed after {grace_ends_at}")]
+    NameNotExpired {
+        name: String,
+        grace_ends_at: Timestamp,
+    },
+    #[error("name {name} can only be reclaimed once its owner approved the manager")]
+    ReclaimNotApproved { name: String },
+    #[error("batch must have between 1 and {max_items} items")]
+    InvalidBatchSize { max_items: usize },
+    #[error("auction of collection {collection} is settled")]
+    AuctionSettled { collection: String },
+    #[error("auction of collection {collection} is not settled")]
+    AuctionNotSettled { collection: String },
+    #[error("auction of collection {collection} has no sales")]
+    NoAuctionSales { collection: String },
+    #[error("auction of collection {collection} must be settled before it is reconfigured")]
+    AuctionReconfigured { collection: String },
+    #[error("no rebate to claim")]
+    NoRebate,
+    #[error("price denom {denom} is listed more than once")]
+    DuplicateDenom { denom: String },
+    #[error("missing payment, any of {:?}", missing_payment)]
+    MissingPayment { missing_payment: Vec<Coin> },
+    #[error("missing CW20 payment {:?}", missing_payment)]
+    MissingCw20Payment { missing_payment: Cw20CoinVerified },
+    #[error("payment in CW20 token {token} is not accepted for this message")]
+    Cw20PaymentNotAccepted { token: String },
+    #[error("mints are only paid in CW20 token {token}")]
+    Cw20PaymentRequired { token: String },
     #[error("{0}")]
     Version(#[from] VersionError),
+    #[error("{0}")]
+    Payment(#[from] PaymentError),
+    #[error("amount cannot be zero")]
+    ZeroAmount,
+    #[error("insufficient balance, only {available} available")]
+    InsufficientBalance { available: Coin },
+    #[error("{0}")]
+    ParseReply(#[from] ParseReplyError),
+    #[error("{0}")]
+    Instantiate2Address(#[from] Instantiate2AddressError),
+    #[error("{0}")]
+    Ownership(#[from] OwnershipError),
+    #[error("{sender} is missing role {role:?}")]
+    MissingRole { sender: String, role: Role },
+    #[error("{kind:?} messages may not be passed through")]
+    PassThroughForbidden { kind: CollectionMsgKind },
+    #[error("collection {collection} is not registered")]
+    CollectionNotRegistered { collection: String },
+    #[error("collection {collection} is already registered")]
+    CollectionAlreadyRegistered { collection: String },
+    #[error("minting and renewing on collection {collection} is paused")]
+    Paused { collection: String },
+    #[error("pause must end after the current height {current_height}")]
+    InvalidPauseHeight { current_height: u64 },
 }
@@ -1,38 +1,960 @@
-use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, Coin, Empty, Uint128};
-use cw721::msg::{Cw721ExecuteMsg, Cw721QueryMsg};
+use cosmwasm_schema::{
+    cw_serde,
+    serde::{Deserialize, Deserializer},
+    QueryResponses,
+};
+use cosmwasm_std::{
+    Addr, Binary, BlockInfo, Coin, Decimal, Empty, HexBinary, StdError, StdResult, Timestamp,
+    Uint128,
+};
+use cw20::{Cw20CoinVerified, Cw20ReceiveMsg};
+use cw721::msg::{Cw721ExecuteMsg, Cw721InstantiateMsg, Cw721QueryMsg};
+use cw_ownable::{cw_ownable_execute, cw_ownable_query};
+use cw_utils::{Expiration, Scheduled};
+use unicode_normalization::{is_nfc, UnicodeNormalization};
+use unicode_script::{Script, UnicodeScript};
 
 use crate::error::ContractError;
 
 #[cw_serde]
 pub struct InstantiateMsg {
     pub payment_params: PaymentParams,
+    /// Defaults to the instantiator.
+    pub owner: Option<String>,
+}
+
+/// The shares of all beneficiaries add up to this.
+pub const TOTAL_SHARE_BPS: u16 = 10_000;
+
+#[cw_serde]
+pub struct Beneficiary {
+    pub address: Addr,
+    /// In basis points of each payment.
+    pub share_bps: u16,
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub enum PayoutMode {
+    /// Sends each beneficiary its share as part of the mint.
+    #[default]
+    Send,
+    /// Credits each beneficiary its share, to be withdrawn later.
+    Ledger,
 }
 
 #[cw_serde]
 pub struct PaymentParams {
-    pub beneficiary: Addr,
-    pub mint_price: Option<Coin>,
+    /// The first beneficiary also receives the rounding remainder of each split. Also read from
+    /// the single `beneficiary` of params saved before, who then receives everything.
+    #[serde(alias = "beneficiary", deserialize_with = "deserialize_beneficiaries")]
+    pub beneficiaries: Vec<Beneficiary>,
+    /// Alternative prices, any one of which pays for a mint. Minting is free when empty, unless
+    /// `cw20_mint_price` is set. Also read from the single optional `mint_price` of params saved
+    /// before.
+    #[serde(
+        alias = "mint_price",
+        default,
+        deserialize_with = "deserialize_mint_prices"
+    )]
+    pub mint_prices: Vec<Coin>,
+    #[serde(default)]
+    pub payout: PayoutMode,
+    /// Price of a mint paid via `ExecuteMsg::Receive`. Always transferred, whatever the payout.
+    /// Only accepted with the fixed pricing rule and without phases.
+    #[serde(default)]
+    pub cw20_mint_price: Option<Cw20CoinVerified>,
+    /// When not empty, mints are only open during one of these phases, the first one active.
+    #[serde(default)]
+    pub phases: Vec<MintPhase>,
+    /// How the price is set outside of phases.
+    #[serde(default)]
+    pub pricing: PricingRule,
+    /// When set, names are registered for a number of years, each paid at the mint price.
+    #[serde(default)]
+    pub subscription: Option<Subscription>,
+}
+
+/// Accepts either a list of beneficiaries or a single address, given all the shares.
+fn deserialize_beneficiaries<'de, D>(deserializer: D) -> Result<Vec<Beneficiary>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(crate = "cosmwasm_schema::serde", untagged)]
+    enum Beneficiaries {
+        Many(Vec<Beneficiary>),
+        Single(Addr),
+    }
+
+    Ok(match Beneficiaries::deserialize(deserializer)? {
+        Beneficiaries::Many(beneficiaries) => beneficiaries,
+        Beneficiaries::Single(address) => vec![Beneficiary {
+            address,
+            share_bps: TOTAL_SHARE_BPS,
+        }],
+    })
+}
+
+/// Accepts either a list of prices or a single optional price.
+fn deserialize_mint_prices<'de, D>(deserializer: D) -> Result<Vec<Coin>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(crate = "cosmwasm_schema::serde", untagged)]
+    enum MintPrices {
+        Many(Vec<Coin>),
+        Single(Option<Coin>),
+    }
+
+    Ok(match MintPrices::deserialize(deserializer)? {
+        MintPrices::Many(mint_prices) => mint_prices,
+        MintPrices::Single(mint_price) => mint_price.into_iter().collect(),
+    })
 }
 
 impl PaymentParams {
     pub fn validate(&self) -> Result<(), ContractError> {
-        match &self.mint_price {
-            Some(coin) if coin.amount.le(&Uint128::zero()) => Err(ContractError::ZeroPrice),
-            None | Some(_) => Ok(()),
+        if let Some(cw20_mint_price) = &self.cw20_mint_price {
+            if cw20_mint_price.amount.is_zero() {
+                return Err(ContractError::ZeroPrice);
+            }
+        }
+        let mut total_bps = 0u32;
+        for (index, beneficiary) in self.beneficiaries.iter().enumerate() {
+            if beneficiary.share_bps == 0 {
+                return Err(ContractError::ZeroShare {
+                    beneficiary: beneficiary.address.to_string(),
+                });
+            }
+            if self.beneficiaries[..index]
+                .iter()
+                .any(|previous| previous.address == beneficiary.address)
+            {
+                return Err(ContractError::DuplicateBeneficiary {
+                    beneficiary: beneficiary.address.to_string(),
+                });
+            }
+            total_bps += u32::from(beneficiary.share_bps);
+        }
+        if total_bps != u32::from(TOTAL_SHARE_BPS) {
+            return Err(ContractError::InvalidShares { total_bps });
+        }
+        validate_mint_prices(&self.mint_prices)?;
+        for (index, phase) in self.phases.iter().enumerate() {
+            if self.phases[..index]
+                .iter()
+                .any(|previous| previous.name == phase.name)
+            {
+                return Err(ContractError::DuplicatePhase {
+                    phase: phase.name.to_owned(),
+                });
+            }
+            validate_mint_prices(&phase.mint_prices)?;
+        }
+        match &self.pricing {
+            PricingRule::Fixed => {}
+            PricingRule::DutchAuction(dutch_auction) => dutch_auction.validate()?,
+            PricingRule::BondingCurve(bonding_curve) => bonding_curve.validate()?,
+            PricingRule::NameLength(name_length_pricing) => name_length_pricing.validate()?,
+        }
+        let fixed_price = matches!(self.pricing, PricingRule::Fixed) && self.phases.is_empty();
+        if self.cw20_mint_price.is_some() && !fixed_price {
+            return Err(ContractError::Cw20PriceNotFixed);
+        }
+        if let Some(subscription) = &self.subscription {
+            if subscription.max_years == 0 {
+                return Err(ContractError::ZeroMaxYears);
+            }
+            // An escrowed payment would be rebated per mint, whatever the years paid for.
+            if matches!(
+                self.pricing,
+                PricingRule::DutchAuction(DutchAuction { rebate: true, .. })
+            ) {
+                return Err(ContractError::RebateWithSubscription);
+            }
+        }
+        Ok(())
+    }
+}
+
+fn validate_mint_prices(mint_prices: &[Coin]) -> Result<(), ContractError> {
+    for (index, coin) in mint_prices.iter().enumerate() {
+        if coin.amount.le(&Uint128::zero()) {
+            return Err(ContractError::ZeroPrice);
+        }
+        if mint_prices[..index]
+            .iter()
+            .any(|previous| previous.denom == coin.denom)
+        {
+            return Err(ContractError::DuplicateDenom {
+                denom: coin.denom.to_owned(),
+            });
+        }
+    }
+    Ok(())
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub enum PricingRule {
+    /// Any one of `PaymentParams::mint_prices`.
+    #[default]
+    Fixed,
+    /// Replaces `PaymentParams::mint_prices`.
+    DutchAuction(DutchAuction),
+    /// Replaces `PaymentParams::mint_prices`.
+    BondingCurve(BondingCurve),
+    /// Replaces `PaymentParams::mint_prices`.
+    NameLength(NameLengthPricing),
+}
+
+pub const SECONDS_PER_YEAR: u64 = 365 * 24 * 60 * 60;
+
+#[cw_serde]
+pub struct Subscription {
+    /// Most years paid for at once.
+    pub max_years: u32,
+    /// Seconds after expiry during which the name can still be renewed, and not yet reclaimed.
+    pub grace_period: u64,
+}
+
+impl Subscription {
+    pub fn validate_years(&self, years: u32) -> Result<(), ContractError> {
+        if years == 0 || years > self.max_years {
+            return Err(ContractError::InvalidYears {
+                max_years: self.max_years,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// A price that starts at `start_price` and drops by `decay_step` every `decay_interval` seconds
+/// from `start_time`, down to `floor_amount`.
+#[cw_serde]
+pub struct DutchAuction {
+    pub start_price: Coin,
+    pub floor_amount: Uint128,
+    pub start_time: Timestamp,
+    pub decay_step: Uint128,
+    pub decay_interval: u64,
+    /// Holds the proceeds until `ExecuteMsg::SettleAuction`, after which each buyer may claim
+    /// back what it paid above the clearing price, the price of the last mint.
+    #[serde(default)]
+    pub rebate: bool,
+}
+
+impl DutchAuction {
+    pub fn validate(&self) -> Result<(), ContractError> {
+        if self.floor_amount.is_zero() {
+            return Err(ContractError::ZeroPrice);
+        }
+        if self.start_price.amount < self.floor_amount {
+            return Err(ContractError::FloorAboveStartPrice {
+                floor_amount: self.floor_amount,
+            });
+        }
+        if self.decay_step.is_zero() || self.decay_interval == 0 {
+            return Err(ContractError::ZeroDecay);
+        }
+        Ok(())
+    }
+
+    /// The start price until `start_time`.
+    pub fn price_at(&self, time: Timestamp) -> Coin {
+        let intervals =
+            time.seconds().saturating_sub(self.start_time.seconds()) / self.decay_interval;
+        let decay = self.decay_step.saturating_mul(Uint128::from(intervals));
+        Coin {
+            denom: self.start_price.denom.to_owned(),
+            amount: self
+                .start_price
+                .amount
+                .saturating_sub(decay)
+                .max(self.floor_amount),
+        }
+    }
+}
+
+/// A price that rises with the collection's token count before the mint.
+#[cw_serde]
+pub struct BondingCurve {
+    pub denom: String,
+    pub curve: Curve,
+}
+
+#[cw_serde]
+pub enum Curve {
+    /// `base + slope * supply`.
+    Linear { base: Uint128, slope: Uint128 },
+    /// `base * (1 + growth_bps / 10000) ^ supply`, rounded down.
+    Exponential { base: Uint128, growth_bps: u16 },
+    /// The price of the last piece whose `from_supply` is reached.
+    Piecewise { pieces: Vec<CurvePiece> },
+}
+
+#[cw_serde]
+pub struct CurvePiece {
+    pub from_supply: u64,
+    pub price: Uint128,
+}
+
+impl BondingCurve {
+    pub fn validate(&self) -> Result<(), ContractError> {
+        match &self.curve {
+            Curve::Linear { base, slope } => {
+                if base.is_zero() {
+                    return Err(ContractError::ZeroPrice);
+                }
+                if slope.is_zero() {
+                    return Err(ContractError::FlatCurve);
+                }
+            }
+            Curve::Exponential { base, growth_bps } => {
+                if base.is_zero() {
+                    return Err(ContractError::ZeroPrice);
+                }
+                if *growth_bps == 0 {
+                    return Err(ContractError::FlatCurve);
+                }
+            }
+            Curve::Piecewise { pieces } => {
+                if pieces.first().map(|piece| piece.from_supply) != Some(0) {
+                    return Err(ContractError::InvalidCurvePieces);
+                }
+                if pieces[0].price.is_zero() {
+                    return Err(ContractError::ZeroPrice);
+                }
+                if pieces.windows(2).any(|pair| {
+                    pair[1].from_supply <= pair[0].from_supply || pair[1].price <= pair[0].price
+                }) {
+                    return Err(ContractError::InvalidCurvePieces);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The price of a mint when the collection already has `supply` tokens.
+    pub fn price_at(&self, supply: u64) -> StdResult<Coin> {
+        let amount = match &self.curve {
+            Curve::Linear { base, slope } => {
+                base.checked_add(slope.checked_mul(Uint128::from(supply))?)?
+            }
+            Curve::Exponential { base, growth_bps } => {
+                let factor = Decimal::from_ratio(
+                    u32::from(TOTAL_SHARE_BPS) + u32::from(*growth_bps),
+                    TOTAL_SHARE_BPS,
+                )
+                .checked_pow(u32::try_from(supply).unwrap_or(u32::MAX))?;
+                base.checked_mul_floor(factor)
+                    .map_err(|err| StdError::generic_err(err.to_string()))?
+            }
+            Curve::Piecewise { pieces } => pieces
+                .iter()
+                .rev()
+                .find(|piece| piece.from_supply <= supply)
+                .map(|piece| piece.price)
+                .unwrap_or_default(),
+        };
+        Ok(Coin {
+            denom: self.denom.to_owned(),
+            amount,
+        })
+    }
+}
+
+/// A price set by the number of characters of the minted `token_id`.
+#[cw_serde]
+pub struct NameLengthPricing {
+    pub denom: String,
+    /// Sorted by `min_length`, starting at 1. A name pays the last tier its length reaches.
+    pub tiers: Vec<LengthTier>,
+    /// Names that pay a fixed price instead of their tier.
+    #[serde(default)]
+    pub premium_names: Vec<PremiumName>,
+}
+
+#[cw_serde]
+pub struct LengthTier {
+    pub min_length: u32,
+    pub price: Uint128,
+}
+
+#[cw_serde]
+pub struct PremiumName {
+    pub name: String,
+    pub price: Uint128,
+}
+
+impl NameLengthPricing {
+    pub fn validate(&self) -> Result<(), ContractError> {
+        if self.tiers.first().map(|tier| tier.min_length) != Some(1)
+            || self
+                .tiers
+                .windows(2)
+                .any(|pair| pair[1].min_length <= pair[0].min_length)
+        {
+            return Err(ContractError::InvalidLengthTiers);
+        }
+        if self.tiers.iter().any(|tier| tier.price.is_zero()) {
+            return Err(ContractError::ZeroPrice);
+        }
+        for (index, premium_name) in self.premium_names.iter().enumerate() {
+            if premium_name.price.is_zero() {
+                return Err(ContractError::ZeroPrice);
+            }
+            if self.premium_names[..index]
+                .iter()
+                .any(|previous| previous.name == premium_name.name)
+            {
+                return Err(ContractError::DuplicatePremiumName {
+                    name: premium_name.name.to_owned(),
+                });
+            }
         }
+        Ok(())
+    }
+
+    /// Returns the price of the name, and whether it is a premium name.
+    pub fn price_of(&self, name: &str) -> (Coin, bool) {
+        if let Some(premium_name) = self
+            .premium_names
+            .iter()
+            .find(|premium_name| premium_name.name == name)
+        {
+            return (
+                Coin {
+                    denom: self.denom.to_owned(),
+                    amount: premium_name.price,
+                },
+                true,
+            );
+        }
+        let length = u32::try_from(name.chars().count()).unwrap_or(u32::MAX);
+        let amount = self
+            .tiers
+            .iter()
+            .rev()
+            .find(|tier| tier.min_length <= length)
+            .map(|tier| tier.price)
+            .unwrap_or_default();
+        (
+            Coin {
+                denom: self.denom.to_owned(),
+                amount,
+            },
+            false,
+        )
+    }
+}
+
+/// Checks on the `token_id` of each mint through the manager.
+#[cw_serde]
+pub struct NameRules {
+    pub charset: Charset,
+    /// In characters.
+    pub min_length: u32,
+    /// In characters.
+    pub max_length: u32,
+    /// Names that only the owner and registrars may mint.
+    #[serde(default)]
+    pub reserved_names: Vec<String>,
+}
+
+/// Lowercase Cyrillic, Greek and Armenian letters that render like Latin ones, so that a name
+/// written only with them spoofs a Latin name.
+const LATIN_CONFUSABLES: &[char] = &[
+    '\u{430}', '\u{441}', '\u{501}', '\u{435}', '\u{4bb}', '\u{456}', '\u{458}', '\u{4cf}',
+    '\u{43e}', '\u{440}', '\u{51b}', '\u{455}', '\u{51d}', '\u{445}', '\u{443}', '\u{3b1}',
+    '\u{3b9}', '\u{3bd}', '\u{3bf}', '\u{3c1}', '\u{3c5}', '\u{570}', '\u{57d}', '\u{585}',
+];
+
+#[cw_serde]
+pub enum Charset {
+    /// Lowercase ASCII letters, digits and hyphens.
+    AsciiLowercase,
+    /// Lowercase letters and digits of a single script, and hyphens, in NFC without compatibility
+    /// characters, so that look-alikes cannot be mixed in. Names of another script made only of
+    /// letters that look like Latin ones are rejected too.
+    Unicode,
+}
+
+impl NameRules {
+    pub fn validate(&self) -> Result<(), ContractError> {
+        if self.min_length == 0 || self.max_length < self.min_length {
+            return Err(ContractError::InvalidNameLengths);
+        }
+        Ok(())
+    }
+
+    pub fn validate_name(&self, name: &str) -> Result<(), ContractError> {
+        let length = u32::try_from(name.chars().count()).unwrap_or(u32::MAX);
+        if length < self.min_length {
+            return Err(ContractError::NameTooShort {
+                min_length: self.min_length,
+            });
+        }
+        if length > self.max_length {
+            return Err(ContractError::NameTooLong {
+                max_length: self.max_length,
+            });
+        }
+        if name.starts_with('-') || name.ends_with('-') {
+            return Err(ContractError::NameHyphenAtEdge);
+        }
+        let allowed = |character: char| match self.charset {
+            Charset::AsciiLowercase => {
+                character.is_ascii_lowercase() || character.is_ascii_digit() || character == '-'
+            }
+            Charset::Unicode => {
+                (character.is_alphanumeric() && character.to_lowercase().eq([character]))
+                    || character == '-'
+            }
+        };
+        if let Some(character) = name.chars().find(|character| !allowed(*character)) {
+            return Err(ContractError::InvalidNameCharacter { character });
+        }
+        if self.charset == Charset::Unicode {
+            if !is_nfc(name) || !name.nfkc().eq(name.chars()) {
+                return Err(ContractError::NameNotNormalized);
+            }
+            let mut scripts = name
+                .chars()
+                .map(|character| character.script())
+                .filter(|script| !matches!(script, Script::Common | Script::Inherited));
+            if let Some(first_script) = scripts.next() {
+                if scripts.any(|script| script != first_script) {
+                    return Err(ContractError::MixedScripts);
+                }
+                if first_script != Script::Latin
+                    && name.chars().all(|character| {
+                        matches!(character.script(), Script::Common | Script::Inherited)
+                            || LATIN_CONFUSABLES.contains(&character)
+                    })
+                {
+                    return Err(ContractError::WholeScriptConfusable);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct MintLimits {
+    /// Maximum number of mints through the manager, per minter.
+    pub max_per_wallet: Option<u64>,
+    /// Maximum number of mints through the manager, also checked against the collection's own
+    /// token count.
+    pub max_supply: Option<u64>,
+}
+
+/// A window during which mints are open, at its own prices.
+#[cw_serde]
+pub struct MintPhase {
+    pub name: String,
+    pub start: Scheduled,
+    pub end: Expiration,
+    /// Replaces `PaymentParams::mint_prices` during the phase.
+    pub mint_prices: Vec<Coin>,
+    /// Maximum number of mints during the phase, per collection.
+    pub cap: Option<u64>,
+    /// Maximum number of mints during the phase, per collection and minter.
+    #[serde(default)]
+    pub max_per_wallet: Option<u64>,
+    /// Restricts minting to the leaves of this Merkle tree. A leaf is the SHA-256 of
+    /// `"{address}:{allocation}"`, and each parent the SHA-256 of its two children, sorted.
+    #[serde(default)]
+    pub allowlist_root: Option<HexBinary>,
+}
+
+impl MintPhase {
+    pub fn is_active(&self, block: &BlockInfo) -> bool {
+        self.start.is_triggered(block) && !self.end.is_expired(block)
     }
 }
 
 pub type CollectionExecuteMsg = Cw721ExecuteMsg<Option<Empty>, Option<Empty>, Empty>;
 pub type CollectionQueryMsg = Cw721QueryMsg<Option<Empty>, Option<Empty>, Empty>;
+pub type CollectionInstantiateMsg = Cw721InstantiateMsg<Option<Empty>>;
 
+#[cw_ownable_execute]
 #[cw_serde]
 pub enum ExecuteMsg {
     PassThrough {
         collection: String,
         message: CollectionExecuteMsg,
+        #[serde(default)]
+        options: Option<PassThroughOptions>,
+    },
+    /// Passes several messages through, paid from the funds sent together, with the change
+    /// refunded once. In `BatchMode::BestEffort`, the response data is a `BatchResponse`.
+    PassThroughBatch {
+        items: Vec<PassThroughItem>,
+        #[serde(default)]
+        mode: BatchMode,
     },
+    /// Instantiates a new collection, with the manager as its minter, and registers it.
+    /// With a `salt`, the collection is instantiated at the address returned by
+    /// `QueryMsg::PredictCollectionAddress`.
+    CreateCollection {
+        code_id: u64,
+        label: String,
+        name: String,
+        symbol: String,
+        creator: Option<String>,
+        withdraw_address: Option<String>,
+        salt: Option<Binary>,
+    },
+    /// The owner's equivalent of `SudoMsg::UpdatePaymentParams`.
+    UpdatePaymentParams(PaymentParams),
+    UpdateCollectionPaymentParams {
+        collection: String,
+        payment_params: PaymentParams,
+    },
+    ClearCollectionPaymentParams {
+        collection: String,
+    },
+    SetMintLimits {
+        collection: String,
+        limits: MintLimits,
+    },
+    SetNameRules {
+        collection: String,
+        rules: NameRules,
+    },
+    ClearNameRules {
+        collection: String,
+    },
+    RegisterCollection {
+        collection: String,
+        label: String,
+    },
+    UnregisterCollection {
+        collection: String,
+    },
+    GrantRole {
+        role: Role,
+        member: String,
+    },
+    RevokeRole {
+        role: Role,
+        member: String,
+    },
+    RenounceRole {
+        role: Role,
+    },
+    SetPassThroughPolicy {
+        kind: CollectionMsgKind,
+        policy: PassThroughPolicy,
+    },
+    /// Stops minting and renewals, on all collections when `collection` is `None`.
+    Pause {
+        collection: Option<String>,
+        until_height: Option<u64>,
+    },
+    Unpause {
+        collection: Option<String>,
+    },
+    /// Withdraws from the sender's balance credited by mints paid in `PayoutMode::Ledger`.
+    Withdraw {
+        denom: String,
+        amount: Uint128,
+    },
+    /// Expects a `ReceiveMsg`.
+    Receive(Cw20ReceiveMsg),
+    /// The treasurer's equivalent of `SudoMsg::SettleAuction`.
+    SettleAuction {
+        collection: String,
+    },
+    /// Sends the sender what it paid above the clearing price of a settled auction.
+    ClaimRebate {
+        collection: String,
+    },
+    /// Extends the registration of a name, from its current expiry, paying the mint price for
+    /// each year. After the grace period, as long as the name was not reclaimed, from now.
+    Renew {
+        collection: String,
+        token_id: String,
+        years: u32,
+    },
+    /// Burns a name whose grace period has ended, once its owner approved the manager on the
+    /// collection. The manager cannot burn names otherwise, so these can still be renewed.
+    ReclaimExpired {
+        collection: String,
+        token_id: String,
+    },
+}
+
+/// Sent embedded in a CW20 `Send` to the manager.
+#[cw_serde]
+pub enum ReceiveMsg {
+    /// Only a `Mint`, paid by the tokens sent.
+    PassThrough {
+        collection: String,
+        message: CollectionExecuteMsg,
+        #[serde(default)]
+        options: Option<PassThroughOptions>,
+    },
+}
+
+/// Most items in a `PassThroughBatch`.
+pub const MAX_BATCH_ITEMS: usize = 30;
+
+#[cw_serde]
+pub struct PassThroughItem {
+    pub collection: String,
+    pub message: CollectionExecuteMsg,
+    #[serde(default)]
+    pub options: Option<PassThroughOptions>,
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub enum BatchMode {
+    /// Reverts every item if any fails.
+    #[default]
+    Atomic,
+    /// Keeps the items the collections accept, and refunds the mint payment of those they
+    /// reject. Items the manager itself rejects still revert the batch.
+    BestEffort,
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct PassThroughOptions {
+    /// Needed to mint during a phase with an allowlist.
+    pub allowlist: Option<AllowlistProof>,
+    /// Years to register the name for, when the payment params have a subscription. Defaults
+    /// to 1.
+    #[serde(default)]
+    pub years: Option<u32>,
+    /// Refunds the mint payment if the collection rejects the message, instead of failing. The
+    /// response data is then a `PassThroughFailure`. Ignored in a batch, see
+    /// `BatchMode::BestEffort`, and when paying in cw20.
+    #[serde(default)]
+    pub catch_errors: bool,
+    /// Sets the response data from what the collection returned, once it accepts the message.
+    /// Ignored in a batch, and when paying in cw20.
+    #[serde(default)]
+    pub return_data: Option<DataFormat>,
+}
+
+#[cw_serde]
+#[derive(Copy)]
+pub enum DataFormat {
+    /// The data of the collection as is, unwrapped from its `MsgExecuteContractResponse`. Left
+    /// unset if the collection returned none.
+    Raw,
+    /// A `PassThroughData`.
+    Envelope,
+}
+
+/// Response data of a pass-through with `DataFormat::Envelope`.
+#[cw_serde]
+pub struct PassThroughData {
+    pub collection: Addr,
+    pub token_count_before: u64,
+    /// Decoded from the data of the collection, when it returns its token count.
+    pub token_count_after: Option<u64>,
+    /// The data of the collection, unwrapped from its `MsgExecuteContractResponse`.
+    pub data: Option<Binary>,
+}
+
+#[cw_serde]
+pub struct AllowlistProof {
+    /// Number of mints allowed to the sender during the phase.
+    pub allocation: u64,
+    /// Sibling hashes, from the leaf up to the root.
+    pub proof: Vec<HexBinary>,
+}
+
+/// Roles granted by the owner. The owner itself passes every role check.
+#[cw_serde]
+#[derive(Copy)]
+pub enum Role {
+    /// May mint through `PassThrough` when the `Mint` policy requires it.
+    Minter,
+    /// May update the global and per-collection payment params.
+    PaymentManager,
+    /// May pass the collection withdraw messages through, and settle auctions.
+    Treasurer,
+    /// May pause and unpause minting.
+    Pauser,
+    /// May mint the reserved names of `NameRules`.
+    Registrar,
+    /// May reclaim expired names.
+    Keeper,
+}
+
+impl Role {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::Minter => "minter",
+            Role::PaymentManager => "payment-manager",
+            Role::Treasurer => "treasurer",
+            Role::Pauser => "pauser",
+            Role::Registrar => "registrar",
+            Role::Keeper => "keeper",
+        }
+    }
+}
+
+/// Who may have the manager pass a given `CollectionExecuteMsg` variant through.
+#[cw_serde]
+pub enum PassThroughPolicy {
+    Anyone,
+    Role(Role),
+    Forbidden,
+}
+
+impl PassThroughPolicy {
+    pub fn to_attribute_value(&self) -> String {
+        match self {
+            PassThroughPolicy::Anyone => "anyone".to_owned(),
+            PassThroughPolicy::Role(role) => format!("role-{}", role.as_str()),
+            PassThroughPolicy::Forbidden => "forbidden".to_owned(),
+        }
+    }
+}
+
+/// The variants of `CollectionExecuteMsg`, without their content.
+#[cw_serde]
+#[derive(Copy)]
+pub enum CollectionMsgKind {
+    UpdateOwnership,
+    UpdateMinterOwnership,
+    UpdateCreatorOwnership,
+    UpdateCollectionInfo,
+    TransferNft,
+    SendNft,
+    Approve,
+    Revoke,
+    ApproveAll,
+    RevokeAll,
+    Mint,
+    Burn,
+    UpdateExtension,
+    UpdateNftInfo,
+    SetWithdrawAddress,
+    RemoveWithdrawAddress,
+    Withdraw,
+}
+
+impl CollectionMsgKind {
+    pub const ALL: [CollectionMsgKind; 17] = [
+        CollectionMsgKind::UpdateOwnership,
+        CollectionMsgKind::UpdateMinterOwnership,
+        CollectionMsgKind::UpdateCreatorOwnership,
+        CollectionMsgKind::UpdateCollectionInfo,
+        CollectionMsgKind::TransferNft,
+        CollectionMsgKind::SendNft,
+        CollectionMsgKind::Approve,
+        CollectionMsgKind::Revoke,
+        CollectionMsgKind::ApproveAll,
+        CollectionMsgKind::RevokeAll,
+        CollectionMsgKind::Mint,
+        CollectionMsgKind::Burn,
+        CollectionMsgKind::UpdateExtension,
+        CollectionMsgKind::UpdateNftInfo,
+        CollectionMsgKind::SetWithdrawAddress,
+        CollectionMsgKind::RemoveWithdrawAddress,
+        CollectionMsgKind::Withdraw,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CollectionMsgKind::UpdateOwnership => "update-ownership",
+            CollectionMsgKind::UpdateMinterOwnership => "update-minter-ownership",
+            CollectionMsgKind::UpdateCreatorOwnership => "update-creator-ownership",
+            CollectionMsgKind::UpdateCollectionInfo => "update-collection-info",
+            CollectionMsgKind::TransferNft => "transfer-nft",
+            CollectionMsgKind::SendNft => "send-nft",
+            CollectionMsgKind::Approve => "approve",
+            CollectionMsgKind::Revoke => "revoke",
+            CollectionMsgKind::ApproveAll => "approve-all",
+            CollectionMsgKind::RevokeAll => "revoke-all",
+            CollectionMsgKind::Mint => "mint",
+            CollectionMsgKind::Burn => "burn",
+            CollectionMsgKind::UpdateExtension => "update-extension",
+            CollectionMsgKind::UpdateNftInfo => "update-nft-info",
+            CollectionMsgKind::SetWithdrawAddress => "set-withdraw-address",
+            CollectionMsgKind::RemoveWithdrawAddress => "remove-withdraw-address",
+            CollectionMsgKind::Withdraw => "withdraw",
+        }
+    }
+
+    /// Anyone may mint, the treasurer handles the collection funds, and everything else
+    /// would let callers act with the manager's own authority over the collection.
+    pub fn default_policy(&self) -> PassThroughPolicy {
+        match self {
+            CollectionMsgKind::Mint => PassThroughPolicy::Anyone,
+            CollectionMsgKind::SetWithdrawAddress
+            | CollectionMsgKind::RemoveWithdrawAddress
+            | CollectionMsgKind::Withdraw => PassThroughPolicy::Role(Role::Treasurer),
+            _ => PassThroughPolicy::Forbidden,
+        }
+    }
+}
+
+impl From<&CollectionExecuteMsg> for CollectionMsgKind {
+    #[allow(deprecated)]
+    fn from(message: &CollectionExecuteMsg) -> Self {
+        match message {
+            CollectionExecuteMsg::UpdateOwnership { .. } => CollectionMsgKind::UpdateOwnership,
+            CollectionExecuteMsg::UpdateMinterOwnership { .. } => {
+                CollectionMsgKind::UpdateMinterOwnership
+            }
+            CollectionExecuteMsg::UpdateCreatorOwnership { .. } => {
+                CollectionMsgKind::UpdateCreatorOwnership
+            }
+            CollectionExecuteMsg::UpdateCollectionInfo { .. } => {
+                CollectionMsgKind::UpdateCollectionInfo
+            }
+            CollectionExecuteMsg::TransferNft { .. } => CollectionMsgKind::TransferNft,
+            CollectionExecuteMsg::SendNft { .. } => CollectionMsgKind::SendNft,
+            CollectionExecuteMsg::Approve { .. } => CollectionMsgKind::Approve,
+            CollectionExecuteMsg::Revoke { .. } => CollectionMsgKind::Revoke,
+            CollectionExecuteMsg::ApproveAll { .. } => CollectionMsgKind::ApproveAll,
+            CollectionExecuteMsg::RevokeAll { .. } => CollectionMsgKind::RevokeAll,
+            CollectionExecuteMsg::Mint { .. } => CollectionMsgKind::Mint,
+            CollectionExecuteMsg::Burn { .. } => CollectionMsgKind::Burn,
+            CollectionExecuteMsg::UpdateExtension { .. } => CollectionMsgKind::UpdateExtension,
+            CollectionExecuteMsg::UpdateNftInfo { .. } => CollectionMsgKind::UpdateNftInfo,
+            CollectionExecuteMsg::SetWithdrawAddress { .. } => {
+                CollectionMsgKind::SetWithdrawAddress
+            }
+            CollectionExecuteMsg::RemoveWithdrawAddress { .. } => {
+                CollectionMsgKind::RemoveWithdrawAddress
+            }
+            CollectionExecuteMsg::Withdraw { .. } => CollectionMsgKind::Withdraw,
+        }
+    }
+}
+
+/// What a collection rejected, in a pass-through catching its errors.
+#[cw_serde]
+pub struct PassThroughFailure {
+    pub collection: Addr,
+    pub kind: CollectionMsgKind,
+    pub error: String,
+}
+
+#[cw_serde]
+pub struct BatchResponse {
+    /// Indices of the items the collections accepted.
+    pub succeeded: Vec<u32>,
+    pub failed: Vec<BatchItemFailure>,
+}
+
+#[cw_serde]
+pub struct BatchItemFailure {
+    pub index: u32,
+    pub error: String,
 }
 
 #[cw_serde]
@@ -40,11 +962,71 @@ pub struct NameServiceExecuteMsgResponse {
     pub num_tokens: u64,
 }
 
+#[cw_ownable_query]
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum QueryMsg {
     #[returns(GetPaymentParamsResponse)]
     GetPaymentParams,
+    #[returns(GetCollectionPaymentParamsResponse)]
+    GetCollectionPaymentParams { collection: String },
+    #[returns(ListCollectionPaymentParamsResponse)]
+    ListCollectionPaymentParams {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(ListCollectionsResponse)]
+    ListCollections {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(PredictCollectionAddressResponse)]
+    PredictCollectionAddress { code_id: u64, salt: Binary },
+    #[returns(HasRoleResponse)]
+    HasRole { role: Role, address: String },
+    #[returns(ListRoleMembersResponse)]
+    ListRoleMembers {
+        role: Role,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(GetPassThroughPoliciesResponse)]
+    GetPassThroughPolicies,
+    #[returns(PauseStatusResponse)]
+    PauseStatus { collection: Option<String> },
+    #[returns(ClaimableBalanceResponse)]
+    ClaimableBalance { beneficiary: String, denom: String },
+    #[returns(CurrentPhaseResponse)]
+    CurrentPhase { collection: String },
+    /// With an `address`, also returns its mints on the collection.
+    #[returns(GetMintLimitsResponse)]
+    GetMintLimits {
+        collection: String,
+        address: Option<String>,
+    },
+    /// Whether `address` may mint now, given its allowlist proof if the phase has an allowlist.
+    #[returns(AllowlistEligibilityResponse)]
+    AllowlistEligibility {
+        collection: String,
+        address: String,
+        allowlist: Option<AllowlistProof>,
+    },
+    /// The prices at which a mint would be paid now, any one of which is enough.
+    #[returns(CurrentMintPriceResponse)]
+    CurrentMintPrice { collection: String },
+    /// The prices of the next `quantity` mints together, any one of which is enough.
+    #[returns(QuoteMintResponse)]
+    QuoteMint { collection: String, quantity: u64 },
+    /// The prices at which the name would be minted now, any one of which is enough.
+    #[returns(QuoteNameResponse)]
+    QuoteName { collection: String, name: String },
+    #[returns(GetNameRulesResponse)]
+    GetNameRules { collection: String },
+    #[returns(NameExpiryResponse)]
+    NameExpiry {
+        collection: String,
+        token_id: String,
+    },
 }
 
 #[cw_serde]
@@ -52,12 +1034,201 @@ pub struct GetPaymentParamsResponse {
     pub payment_params: PaymentParams,
 }
 
+#[cw_serde]
+pub struct GetCollectionPaymentParamsResponse {
+    pub collection: Addr,
+    /// The params applied to mints of this collection.
+    pub payment_params: PaymentParams,
+    /// `true` when the collection has no params of its own and the global ones apply.
+    pub is_default: bool,
+}
+
+#[cw_serde]
+pub struct CollectionPaymentParams {
+    pub collection: Addr,
+    pub payment_params: PaymentParams,
+}
+
+#[cw_serde]
+pub struct ListCollectionPaymentParamsResponse {
+    pub collections: Vec<CollectionPaymentParams>,
+}
+
+/// What the manager knows about a collection it is allowed to pass messages through to.
+#[cw_serde]
+pub struct CollectionRecord {
+    pub label: String,
+    pub registered_at_height: u64,
+}
+
+#[cw_serde]
+pub struct ManagedCollection {
+    pub collection: Addr,
+    pub record: CollectionRecord,
+}
+
+#[cw_serde]
+pub struct ListCollectionsResponse {
+    pub collections: Vec<ManagedCollection>,
+}
+
+#[cw_serde]
+pub struct PredictCollectionAddressResponse {
+    pub address: Addr,
+}
+
+#[cw_serde]
+pub struct HasRoleResponse {
+    pub has_role: bool,
+}
+
+#[cw_serde]
+pub struct ListRoleMembersResponse {
+    pub members: Vec<Addr>,
+}
+
+#[cw_serde]
+pub struct KindPassThroughPolicy {
+    pub kind: CollectionMsgKind,
+    pub policy: PassThroughPolicy,
+}
+
+#[cw_serde]
+pub struct GetPassThroughPoliciesResponse {
+    pub policies: Vec<KindPassThroughPolicy>,
+}
+
+/// A pause with no `until_height` lasts until explicitly lifted.
+#[cw_serde]
+pub struct PauseInfo {
+    pub until_height: Option<u64>,
+}
+
+impl PauseInfo {
+    pub fn is_active(&self, height: u64) -> bool {
+        match self.until_height {
+            None => true,
+            Some(until_height) => height < until_height,
+        }
+    }
+}
+
+#[cw_serde]
+pub struct PauseStatusResponse {
+    pub paused: bool,
+    /// Only present while active.
+    pub global: Option<PauseInfo>,
+    /// Only present while active.
+    pub collection: Option<PauseInfo>,
+}
+
+#[cw_serde]
+pub struct GetMintLimitsResponse {
+    pub limits: MintLimits,
+    pub minted: u64,
+    pub minted_by_address: Option<u64>,
+}
+
+#[cw_serde]
+pub struct CurrentPhaseResponse {
+    /// Whether the collection's payment params have phases at all.
+    pub has_phases: bool,
+    pub phase: Option<MintPhase>,
+    /// Mints so far during `phase`.
+    pub minted: u64,
+}
+
+#[cw_serde]
+pub struct AllowlistEligibilityResponse {
+    pub eligible: bool,
+    /// The active phase, if any.
+    pub phase: Option<String>,
+    /// Mints left in the allocation, when the active phase has an allowlist and the proof is valid.
+    pub remaining_allocation: Option<u64>,
+}
+
+#[cw_serde]
+pub struct CurrentMintPriceResponse {
+    pub mint_prices: Vec<Coin>,
+    /// The active phase, whose prices apply, if any.
+    pub phase: Option<String>,
+}
+
+#[cw_serde]
+pub struct QuoteMintResponse {
+    pub totals: Vec<Coin>,
+}
+
+#[cw_serde]
+pub struct QuoteNameResponse {
+    pub mint_prices: Vec<Coin>,
+    /// Whether the name is priced from `NameLengthPricing::premium_names`.
+    pub premium: bool,
+}
+
+#[cw_serde]
+pub struct GetNameRulesResponse {
+    /// Any `token_id` may be minted when `None`.
+    pub rules: Option<NameRules>,
+}
+
+#[cw_serde]
+pub struct NameExpiryResponse {
+    /// `None` when the name was not registered with a subscription.
+    pub expires_at: Option<Timestamp>,
+    /// After which the name may be reclaimed.
+    pub grace_ends_at: Option<Timestamp>,
+}
+
+#[cw_serde]
+pub struct ClaimableBalanceResponse {
+    pub balance: Coin,
+}
+
 #[cw_serde]
 pub enum SudoMsg {
     UpdatePaymentParams(PaymentParams),
+    UpdateCollectionPaymentParams {
+        collection: String,
+        payment_params: PaymentParams,
+    },
+    ClearCollectionPaymentParams {
+        collection: String,
+    },
+    SetMintLimits {
+        collection: String,
+        limits: MintLimits,
+    },
+    SetNameRules {
+        collection: String,
+        rules: NameRules,
+    },
+    ClearNameRules {
+        collection: String,
+    },
+    RegisterCollection {
+        collection: String,
+        label: String,
+    },
+    UnregisterCollection {
+        collection: String,
+    },
+    Pause {
+        collection: Option<String>,
+        until_height: Option<u64>,
+    },
+    Unpause {
+        collection: Option<String>,
+    },
+    /// Pays the beneficiaries the clearing price of each mint of the collection's auction with a
+    /// rebate, and opens the rebates to claims. Ends the auction.
+    SettleAuction {
+        collection: String,
+    },
 }
 
 #[cw_serde]
 pub struct MigrateMsg {
     pub payment_params: PaymentParams,
+    pub owner: Option<String>,
 }
@@ -1,8 +1,109 @@
-use cw_storage_plus::Item;
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Coin, Empty, Timestamp, Uint128};
+use cw_storage_plus::{Item, Map};
 
-use crate::msg::PaymentParams;
+use crate::msg::{
+    BatchItemFailure, CollectionMsgKind, CollectionRecord, DataFormat, MintLimits, NameRules,
+    PassThroughPolicy, PauseInfo, PaymentParams,
+};
 
 pub const CONTRACT_NAME: &str = "my-collection-manager";
 pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 pub const PAYMENT_PARAMS: Item<PaymentParams> = Item::new("payment_params");
+pub const COLLECTION_PAYMENT_PARAMS: Map<&Addr, PaymentParams> =
+    Map::new("collection_payment_params");
+pub const COLLECTIONS: Map<&Addr, CollectionRecord> = Map::new("collections");
+/// Label of the collection being instantiated, until its address comes back in the reply.
+pub const PENDING_COLLECTION_LABEL: Item<String> = Item::new("pending_collection_label");
+/// Members of each role, keyed by `Role::as_str`.
+pub const ROLE_MEMBERS: Map<(&str, &Addr), Empty> = Map::new("role_members");
+/// Overrides of `CollectionMsgKind::default_policy`, keyed by `CollectionMsgKind::as_str`.
+pub const PASS_THROUGH_POLICIES: Map<&str, PassThroughPolicy> = Map::new("pass_through_policies");
+/// Credited by mints paid in `PayoutMode::Ledger`, keyed by beneficiary and denom.
+pub const CLAIMABLE_BALANCES: Map<(&Addr, &str), Uint128> = Map::new("claimable_balances");
+/// Mints so far, keyed by collection and `MintPhase::name`.
+pub const PHASE_MINT_COUNTS: Map<(&Addr, &str), u64> = Map::new("phase_mint_counts");
+/// Mints so far, keyed by collection, `MintPhase::name` and minter.
+pub const PHASE_WALLET_MINTS: Map<(&Addr, &str, &Addr), u64> = Map::new("phase_wallet_mints");
+pub const COLLECTION_NAME_RULES: Map<&Addr, NameRules> = Map::new("collection_name_rules");
+pub const COLLECTION_MINT_LIMITS: Map<&Addr, MintLimits> = Map::new("collection_mint_limits");
+/// Mints through the manager so far, keyed by collection.
+pub const COLLECTION_MINTS: Map<&Addr, u64> = Map::new("collection_mints");
+/// Mints through the manager so far, keyed by collection and minter.
+pub const WALLET_MINTS: Map<(&Addr, &Addr), u64> = Map::new("wallet_mints");
+/// Sales of a Dutch auction with a rebate, keyed by collection and auction start, in seconds.
+pub const AUCTION_SALES: Map<(&Addr, u64), AuctionSales> = Map::new("auction_sales");
+/// Purchases in a Dutch auction with a rebate, keyed by collection, buyer and auction start, in
+/// seconds.
+pub const AUCTION_PURCHASES: Map<(&Addr, &Addr, u64), AuctionPurchase> =
+    Map::new("auction_purchases");
+/// Registrations with a subscription, keyed by collection and `token_id`.
+pub const NAME_EXPIRIES: Map<(&Addr, &str), Timestamp> = Map::new("name_expiries");
+/// Best-effort batches passed through so far, numbering their replies.
+pub const BATCH_COUNT: Item<u64> = Item::new("batch_count");
+/// Items of the best-effort batches whose replies are still to come, keyed by batch number, so
+/// that a batch passed through while another is pending does not overwrite it.
+pub const PENDING_BATCHES: Map<u64, PendingBatch> = Map::new("pending_batches");
+/// Pass-throughs held until their reply so far, numbering their replies.
+pub const PASS_THROUGH_COUNT: Item<u64> = Item::new("pass_through_count");
+/// The pass-throughs catching the errors of the collection, or returning its data, until they
+/// reply, keyed by reply id, so that a pass-through sent while another is pending does not
+/// overwrite it.
+pub const PENDING_PASS_THROUGHS: Map<u64, PendingPassThrough> = Map::new("pending_pass_throughs");
+pub const GLOBAL_PAUSE: Item<PauseInfo> = Item::new("global_pause");
+pub const COLLECTION_PAUSES: Map<&Addr, PauseInfo> = Map::new("collection_pauses");
+
+#[cw_serde]
+pub struct AuctionSales {
+    /// Price of the last mint, which is the clearing price once settled.
+    pub last_price: Coin,
+    pub minted: u64,
+    pub settled: bool,
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct AuctionPurchase {
+    pub minted: u64,
+    pub paid: Uint128,
+}
+
+#[cw_serde]
+pub struct PendingBatch {
+    pub minter: Addr,
+    pub items: Vec<PendingBatchItem>,
+    pub succeeded: Vec<u32>,
+    pub failed: Vec<BatchItemFailure>,
+}
+
+#[cw_serde]
+pub struct PendingPassThrough {
+    pub minter: Addr,
+    pub collection: Addr,
+    pub kind: CollectionMsgKind,
+    /// Held until the reply, when catching the errors of the collection.
+    pub mint: Option<PendingMint>,
+    pub token_count_before: u64,
+    pub return_data: Option<DataFormat>,
+}
+
+#[cw_serde]
+pub struct PendingBatchItem {
+    pub collection: Addr,
+    pub mint: Option<PendingMint>,
+}
+
+/// A mint counted and priced, whose payment is held until the collection accepts it.
+#[cw_serde]
+pub struct PendingMint {
+    pub token_id: String,
+    pub payment: Option<Coin>,
+    /// Start, in seconds, of the auction whose escrow gets the payment rather than the
+    /// beneficiaries.
+    pub escrow_auction: Option<u64>,
+    pub phase: Option<String>,
+    /// Whether the mint registered the name with a subscription, replacing `previous_expiry`.
+    pub subscribed: bool,
+    pub previous_expiry: Option<Timestamp>,
+}
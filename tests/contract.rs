@@ -1,14 +1,38 @@
 use std::fmt::Error;
 
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Coin, DepsMut, Empty, Env, Event, MessageInfo, Response, Uint128};
+use cosmwasm_std::{
+    from_json, to_json_binary, Addr, Binary, Coin, DepsMut, Empty, Env, Event, HexBinary,
+    MessageInfo, Response, Timestamp, Uint128,
+};
+use cw20::{BalanceResponse, Cw20Coin, Cw20CoinVerified, Cw20ExecuteMsg, Cw20QueryMsg};
+use cw20_base::{
+    contract::{execute as execute_cw20, instantiate as instantiate_cw20, query as query_cw20},
+    msg::InstantiateMsg as Cw20InstantiateMsg,
+};
 use cw721::msg::{Cw721ExecuteMsg, Cw721QueryMsg, OwnerOfResponse};
-use cw_multi_test::{App, AppBuilder, ContractWrapper, Executor, WasmSudo};
+use cw_multi_test::{
+    App, AppBuilder, ContractWrapper, Executor, MockAddressGenerator, MockApiBech32, WasmKeeper,
+    WasmSudo,
+};
+use cw_ownable::{Action, Ownership};
+use cw_utils::{Expiration, Scheduled};
 use my_collection_manager::{
     contract::{execute, instantiate, migrate, query, reply, sudo},
     msg::{
-        ExecuteMsg, GetPaymentParamsResponse, InstantiateMsg, MigrateMsg, PaymentParams, QueryMsg,
-        SudoMsg,
+        AllowlistEligibilityResponse, AllowlistProof, BatchMode, BatchResponse, Beneficiary,
+        BondingCurve, Charset, ClaimableBalanceResponse, CollectionMsgKind,
+        CollectionPaymentParams, CollectionRecord, CurrentPhaseResponse, Curve, CurvePiece,
+        DataFormat, DutchAuction, ExecuteMsg, GetCollectionPaymentParamsResponse,
+        GetMintLimitsResponse, GetNameRulesResponse, GetPassThroughPoliciesResponse,
+        GetPaymentParamsResponse, HasRoleResponse, InstantiateMsg, KindPassThroughPolicy,
+        LengthTier, ListCollectionPaymentParamsResponse, ListCollectionsResponse,
+        ListRoleMembersResponse, ManagedCollection, MigrateMsg, MintLimits, MintPhase,
+        NameExpiryResponse, NameLengthPricing, NameRules, NameServiceExecuteMsgResponse,
+        PassThroughData, PassThroughFailure, PassThroughItem, PassThroughOptions,
+        PassThroughPolicy, PauseInfo, PauseStatusResponse, PaymentParams, PayoutMode,
+        PredictCollectionAddressResponse, PremiumName, PricingRule, QueryMsg, QuoteMintResponse,
+        QuoteNameResponse, ReceiveMsg, Role, Subscription, SudoMsg, SECONDS_PER_YEAR,
     },
 };
 use my_nameservice::{
@@ -18,17 +42,22 @@ use my_nameservice::{
     },
     msg::InstantiateMsg as MyNameserviceInstantiateMsg,
 };
+use sha2::{Digest, Sha256};
 
 pub type CollectionExecuteMsg = Cw721ExecuteMsg<Option<Empty>, Option<Empty>, Empty>;
 pub type CollectionQueryMsg = Cw721QueryMsg<Option<Empty>, Option<Empty>, Empty>;
 
-fn instantiate_nameservice(mock_app: &mut App, minter: String) -> (u64, Addr) {
+fn store_nameservice_code(mock_app: &mut App) -> u64 {
     let nameservice_code = Box::new(ContractWrapper::new(
         execute_my_nameservice,
         instantiate_my_nameservice,
         query_my_nameservice,
     ));
-    let nameservice_code_id = mock_app.store_code(nameservice_code);
+    mock_app.store_code(nameservice_code)
+}
+
+fn instantiate_nameservice(mock_app: &mut App, minter: String) -> (u64, Addr) {
+    let nameservice_code_id = store_nameservice_code(mock_app);
     return (
         nameservice_code_id,
         mock_app
@@ -51,6 +80,35 @@ fn instantiate_nameservice(mock_app: &mut App, minter: String) -> (u64, Addr) {
     );
 }
 
+fn register_collection(mock_app: &mut App, addr_manager: &Addr, addr_collection: &Addr) {
+    let register_sudo_msg = SudoMsg::RegisterCollection {
+        collection: addr_collection.to_string(),
+        label: "my names".to_owned(),
+    };
+    let _ = mock_app
+        .sudo(cw_multi_test::SudoMsg::Wasm(
+            WasmSudo::new(addr_manager, &register_sudo_msg)
+                .expect("Failed to serialize sudo message"),
+        ))
+        .expect("Failed to register collection");
+}
+
+/// Pays everything to one beneficiary at the given prices, with every other option left off.
+fn payment_params(beneficiary: Addr, mint_prices: Vec<Coin>) -> PaymentParams {
+    PaymentParams {
+        beneficiaries: vec![Beneficiary {
+            address: beneficiary,
+            share_bps: 10_000,
+        }],
+        mint_prices,
+        payout: PayoutMode::Send,
+        cw20_mint_price: None,
+        phases: vec![],
+        pricing: PricingRule::Fixed,
+        subscription: None,
+    }
+}
+
 fn instantiate_collection_manager(
     mock_app: &mut App,
     payment_params: PaymentParams,
@@ -68,7 +126,10 @@ fn instantiate_collection_manager(
             .instantiate_contract(
                 manager_code_id,
                 Addr::unchecked("deployer-manager"),
-                &InstantiateMsg { payment_params },
+                &InstantiateMsg {
+                    payment_params,
+                    owner: None,
+                },
                 &[],
                 "my-collection-manager",
                 None,
@@ -84,12 +145,10 @@ fn test_mint_through() {
     let beneficiary_addr = Addr::unchecked("beneficiary");
     let (_, addr_manager) = instantiate_collection_manager(
         &mut mock_app,
-        PaymentParams {
-            beneficiary: beneficiary_addr.to_owned(),
-            mint_price: None,
-        },
+        payment_params(beneficiary_addr.to_owned(), vec![]),
     );
     let (_, addr_collection) = instantiate_nameservice(&mut mock_app, addr_manager.to_string());
+    register_collection(&mut mock_app, &addr_manager, &addr_collection);
     let owner_addr = Addr::unchecked("owner");
     let name_alice = "alice".to_owned();
     let sender_addr = Addr::unchecked("sender");
@@ -101,6 +160,7 @@ fn test_mint_through() {
             token_uri: None,
             extension: None,
         },
+        options: None,
     };
 
     // Act
@@ -143,6 +203,220 @@ fn test_mint_through() {
     );
 }
 
+#[test]
+fn test_mint_through_requires_registration() {
+    // Arrange
+    let mut mock_app = App::default();
+    let (_, addr_manager) = instantiate_collection_manager(
+        &mut mock_app,
+        payment_params(Addr::unchecked("beneficiary"), vec![]),
+    );
+    let (_, addr_collection) = instantiate_nameservice(&mut mock_app, addr_manager.to_string());
+    let register_msg = ExecuteMsg::PassThrough {
+        collection: addr_collection.to_string(),
+        message: CollectionExecuteMsg::Mint {
+            token_id: "alice".to_owned(),
+            owner: "owner".to_owned(),
+            token_uri: None,
+            extension: None,
+        },
+        options: None,
+    };
+    let result = mock_app.execute_contract(
+        Addr::unchecked("sender"),
+        addr_manager.clone(),
+        &register_msg,
+        &[],
+    );
+    assert!(
+        result.is_err(),
+        "Should not pass through to unregistered collection"
+    );
+
+    // Act
+    register_collection(&mut mock_app, &addr_manager, &addr_collection);
+    let result = mock_app.execute_contract(
+        Addr::unchecked("sender"),
+        addr_manager.clone(),
+        &register_msg,
+        &[],
+    );
+
+    // Assert
+    assert!(result.is_ok(), "Failed to pass through the message");
+    let result = mock_app.wrap().query_wasm_smart::<ListCollectionsResponse>(
+        &addr_manager,
+        &QueryMsg::ListCollections {
+            start_after: None,
+            limit: None,
+        },
+    );
+    assert!(result.is_ok(), "Failed to list collections");
+    assert_eq!(
+        result.unwrap(),
+        ListCollectionsResponse {
+            collections: vec![ManagedCollection {
+                collection: addr_collection,
+                record: CollectionRecord {
+                    label: "my names".to_owned(),
+                    registered_at_height: mock_app.block_info().height,
+                },
+            }],
+        }
+    );
+}
+
+#[test]
+fn test_create_collection_and_mint_through() {
+    // Arrange
+    let mut mock_app = App::default();
+    let (_, addr_manager) = instantiate_collection_manager(
+        &mut mock_app,
+        payment_params(Addr::unchecked("beneficiary"), vec![]),
+    );
+    let nameservice_code_id = store_nameservice_code(&mut mock_app);
+    let create_msg = ExecuteMsg::CreateCollection {
+        code_id: nameservice_code_id,
+        label: "my names".to_owned(),
+        name: "my names".to_owned(),
+        symbol: "MYN".to_owned(),
+        creator: None,
+        withdraw_address: None,
+        salt: None,
+    };
+
+    // Act
+    let result = mock_app.execute_contract(
+        Addr::unchecked("deployer-manager"),
+        addr_manager.clone(),
+        &create_msg,
+        &[],
+    );
+
+    // Assert
+    assert!(result.is_ok(), "Failed to create collection");
+    let collections = mock_app
+        .wrap()
+        .query_wasm_smart::<ListCollectionsResponse>(
+            &addr_manager,
+            &QueryMsg::ListCollections {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .expect("Failed to list collections")
+        .collections;
+    assert_eq!(collections.len(), 1);
+    let addr_collection = collections[0].collection.to_owned();
+    result.unwrap().assert_event(
+        &Event::new("wasm-my-collection-manager")
+            .add_attribute("create-collection", addr_collection.to_string()),
+    );
+    let register_msg = ExecuteMsg::PassThrough {
+        collection: addr_collection.to_string(),
+        message: CollectionExecuteMsg::Mint {
+            token_id: "alice".to_owned(),
+            owner: "owner".to_owned(),
+            token_uri: None,
+            extension: None,
+        },
+        options: None,
+    };
+    let result = mock_app.execute_contract(
+        Addr::unchecked("sender"),
+        addr_manager.clone(),
+        &register_msg,
+        &[],
+    );
+    assert!(result.is_ok(), "Failed to mint through the new collection");
+    let owner = mock_app
+        .wrap()
+        .query_wasm_smart::<OwnerOfResponse>(
+            addr_collection,
+            &CollectionQueryMsg::OwnerOf {
+                token_id: "alice".to_owned(),
+                include_expired: None,
+            },
+        )
+        .expect("Failed to query alice name");
+    assert_eq!(owner.owner, "owner");
+}
+
+#[test]
+fn test_create_collection_at_predicted_address() {
+    // Arrange
+    let mut mock_app = AppBuilder::default()
+        .with_api(MockApiBech32::new("cosmwasm"))
+        .with_wasm(WasmKeeper::default().with_address_generator(MockAddressGenerator))
+        .build(|_, _, _| {});
+    let deployer_addr = mock_app.api().addr_make("deployer");
+    let manager_code_id = mock_app.store_code(Box::new(
+        ContractWrapper::new(execute, instantiate, query).with_reply(reply),
+    ));
+    let addr_manager = mock_app
+        .instantiate_contract(
+            manager_code_id,
+            deployer_addr.to_owned(),
+            &InstantiateMsg {
+                payment_params: payment_params(deployer_addr.to_owned(), vec![]),
+                owner: None,
+            },
+            &[],
+            "my-collection-manager",
+            None,
+        )
+        .expect("Failed to instantiate collection manager");
+    let nameservice_code_id = mock_app.store_code(Box::new(ContractWrapper::new(
+        execute_my_nameservice,
+        instantiate_my_nameservice,
+        query_my_nameservice,
+    )));
+    let salt = Binary::from(b"my-names".to_vec());
+    let predicted = mock_app
+        .wrap()
+        .query_wasm_smart::<PredictCollectionAddressResponse>(
+            &addr_manager,
+            &QueryMsg::PredictCollectionAddress {
+                code_id: nameservice_code_id,
+                salt: salt.to_owned(),
+            },
+        )
+        .expect("Failed to predict collection address")
+        .address;
+    let create_msg = ExecuteMsg::CreateCollection {
+        code_id: nameservice_code_id,
+        label: "my names".to_owned(),
+        name: "my names".to_owned(),
+        symbol: "MYN".to_owned(),
+        creator: None,
+        withdraw_address: None,
+        salt: Some(salt),
+    };
+
+    // Act
+    let result = mock_app.execute_contract(deployer_addr, addr_manager.clone(), &create_msg, &[]);
+
+    // Assert
+    assert!(result.is_ok(), "Failed to create collection");
+    result.unwrap().assert_event(
+        &Event::new("wasm-my-collection-manager")
+            .add_attribute("create-collection", predicted.to_string()),
+    );
+    let collections = mock_app
+        .wrap()
+        .query_wasm_smart::<ListCollectionsResponse>(
+            &addr_manager,
+            &QueryMsg::ListCollections {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .expect("Failed to list collections")
+        .collections;
+    assert_eq!(collections.len(), 1);
+    assert_eq!(collections[0].collection, predicted);
+}
+
 #[test]
 fn test_paid_mint_through() {
     // Arrange
@@ -172,12 +446,10 @@ fn test_paid_mint_through() {
     let beneficiary = Addr::unchecked("beneficiary");
     let (_, addr_manager) = instantiate_collection_manager(
         &mut mock_app,
-        PaymentParams {
-            beneficiary: beneficiary.to_owned(),
-            mint_price: Some(minting_price.to_owned()),
-        },
+        payment_params(beneficiary.to_owned(), vec![minting_price.to_owned()]),
     );
     let (_, addr_collection) = instantiate_nameservice(&mut mock_app, addr_manager.to_string());
+    register_collection(&mut mock_app, &addr_manager, &addr_collection);
     let owner_addr = Addr::unchecked("owner");
     let name_alice = "alice".to_owned();
     let register_msg = ExecuteMsg::PassThrough {
@@ -188,6 +460,7 @@ fn test_paid_mint_through() {
             token_uri: None,
             extension: None,
         },
+        options: None,
     };
     let half_silver = Coin {
         amount: Uint128::from(30u16),
@@ -254,142 +527,1757 @@ fn test_paid_mint_through() {
 }
 
 #[test]
-fn test_mint_num_tokens() {
+fn test_ledger_mint_through_and_withdraw() {
     // Arrange
-    let mut mock_app = App::default();
-    let beneficiary_addr = Addr::unchecked("beneficiary");
+    let sender_addr = Addr::unchecked("sender");
+    let minting_price = Coin {
+        amount: Uint128::from(55u16),
+        denom: "silver".to_owned(),
+    };
+    let mut mock_app = AppBuilder::default().build(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(
+                storage,
+                &sender_addr,
+                vec![Coin {
+                    amount: Uint128::from(110u16),
+                    denom: "silver".to_owned(),
+                }],
+            )
+            .expect("Failed to init bank balances");
+    });
+    let creator_addr = Addr::unchecked("creator");
+    let platform_addr = Addr::unchecked("platform");
     let (_, addr_manager) = instantiate_collection_manager(
         &mut mock_app,
         PaymentParams {
-            beneficiary: beneficiary_addr.to_owned(),
-            mint_price: None,
+            beneficiaries: vec![
+                Beneficiary {
+                    address: creator_addr.to_owned(),
+                    share_bps: 7_000,
+                },
+                Beneficiary {
+                    address: platform_addr.to_owned(),
+                    share_bps: 3_000,
+                },
+            ],
+            mint_prices: vec![minting_price.to_owned()],
+            payout: PayoutMode::Ledger,
+            cw20_mint_price: None,
+            phases: vec![],
+            pricing: PricingRule::Fixed,
+            subscription: None,
         },
     );
     let (_, addr_collection) = instantiate_nameservice(&mut mock_app, addr_manager.to_string());
-    let owner_addr = Addr::unchecked("owner");
-    let name_alice = "alice".to_owned();
-    let name_bob = "bob".to_owned();
-    let sender_addr = Addr::unchecked("sender");
-    let register_msg = ExecuteMsg::PassThrough {
-        collection: addr_collection.to_string(),
-        message: CollectionExecuteMsg::Mint {
-            token_id: name_alice.clone(),
-            owner: owner_addr.to_string(),
-            token_uri: None,
-            extension: None,
-        },
+    register_collection(&mut mock_app, &addr_manager, &addr_collection);
+    for name in ["alice", "bob"] {
+        let _ = mock_app
+            .execute_contract(
+                sender_addr.clone(),
+                addr_manager.clone(),
+                &ExecuteMsg::PassThrough {
+                    collection: addr_collection.to_string(),
+                    message: CollectionExecuteMsg::Mint {
+                        token_id: name.to_owned(),
+                        owner: "owner".to_owned(),
+                        token_uri: None,
+                        extension: None,
+                    },
+                    options: None,
+                },
+                &[minting_price.to_owned()],
+            )
+            .expect("Failed to mint through");
+    }
+    let claimable = |mock_app: &App, beneficiary: &Addr| {
+        mock_app
+            .wrap()
+            .query_wasm_smart::<ClaimableBalanceResponse>(
+                &addr_manager,
+                &QueryMsg::ClaimableBalance {
+                    beneficiary: beneficiary.to_string(),
+                    denom: "silver".to_owned(),
+                },
+            )
+            .expect("Failed to query claimable balance")
+            .balance
+            .amount
     };
-    let _ = mock_app
-        .execute_contract(
-            sender_addr.clone(),
-            addr_manager.clone(),
-            &register_msg,
-            &[],
-        )
-        .expect("Failed to pass through the first mint message");
-    let register_msg = ExecuteMsg::PassThrough {
-        collection: addr_collection.to_string(),
-        message: CollectionExecuteMsg::Mint {
-            token_id: name_bob.clone(),
-            owner: owner_addr.to_string(),
-            token_uri: None,
-            extension: None,
-        },
+    let manager_balance = |mock_app: &App| {
+        mock_app
+            .wrap()
+            .query_balance(&addr_manager, "silver")
+            .expect("Failed to get manager balance")
+            .amount
     };
-
-    // Act
+    assert_eq!(claimable(&mock_app, &creator_addr), Uint128::from(78u16));
+    assert_eq!(claimable(&mock_app, &platform_addr), Uint128::from(32u16));
+    assert_eq!(manager_balance(&mock_app), Uint128::from(110u16));
     let result = mock_app.execute_contract(
-        sender_addr.clone(),
+        platform_addr.to_owned(),
         addr_manager.clone(),
-        &register_msg,
+        &ExecuteMsg::Withdraw {
+            denom: "silver".to_owned(),
+            amount: Uint128::from(33u16),
+        },
         &[],
     );
+    assert!(result.is_err(), "Should not withdraw more than credited");
+
+    // Act
+    let result = mock_app
+        .execute_contract(
+            creator_addr.to_owned(),
+            addr_manager.clone(),
+            &ExecuteMsg::Withdraw {
+                denom: "silver".to_owned(),
+                amount: Uint128::from(50u16),
+            },
+            &[],
+        )
+        .expect("Failed to withdraw");
 
     // Assert
-    assert!(
-        result.is_ok(),
-        "Failed to pass through the second mint message"
-    );
-    let result = result.unwrap();
-    let expected_cw721_event = Event::new("wasm")
-        .add_attribute("_contract_address", addr_collection.to_string())
-        .add_attribute("action", "mint")
-        .add_attribute("token_id", name_bob.to_string())
-        .add_attribute("owner", owner_addr.to_string());
-    result.assert_event(&expected_cw721_event);
-    let expected_manager_event =
-        Event::new("wasm-my-collection-manager").add_attribute("token-count-before", "1");
-    result.assert_event(&expected_manager_event);
-    let expected_manager_event =
-        Event::new("wasm-my-collection-manager").add_attribute("token-count-after", "2");
-    result.assert_event(&expected_manager_event);
+    let expected_event = Event::new("wasm-my-collection-manager")
+        .add_attribute("withdraw-beneficiary", "creator")
+        .add_attribute("withdraw-denom", "silver")
+        .add_attribute("withdraw-amount", "50");
+    result.assert_event(&expected_event);
+    assert_eq!(claimable(&mock_app, &creator_addr), Uint128::from(28u16));
     assert_eq!(
-        mock_app
-            .wrap()
-            .query_wasm_smart::<OwnerOfResponse>(
-                addr_collection.to_owned(),
-                &CollectionQueryMsg::OwnerOf {
-                    token_id: name_alice.to_string(),
-                    include_expired: None,
-                }
-            )
-            .expect("Failed to query alice name"),
-        OwnerOfResponse {
-            owner: owner_addr.to_string(),
-            approvals: vec![],
-        }
+        manager_balance(&mock_app),
+        claimable(&mock_app, &creator_addr) + claimable(&mock_app, &platform_addr)
     );
     assert_eq!(
         mock_app
             .wrap()
-            .query_wasm_smart::<OwnerOfResponse>(
-                addr_collection,
-                &CollectionQueryMsg::OwnerOf {
-                    token_id: name_bob.to_string(),
-                    include_expired: None,
-                }
-            )
-            .expect("Failed to query bob name"),
-        OwnerOfResponse {
-            owner: owner_addr.to_string(),
-            approvals: vec![],
-        }
+            .query_balance(&creator_addr, "silver")
+            .expect("Failed to get creator balance")
+            .amount,
+        Uint128::from(50u16)
     );
 }
 
 #[test]
-fn test_sudo_update_payment_params() {
+fn test_dutch_auction_rebate() {
     // Arrange
-    let mut mock_app = App::default();
-    let beneficiary_addr = Addr::unchecked("beneficiary");
+    let early_addr = Addr::unchecked("early");
+    let late_addr = Addr::unchecked("late");
+    let mut mock_app = AppBuilder::default().build(|router, _api, storage| {
+        for buyer in [&early_addr, &late_addr] {
+            router
+                .bank
+                .init_balance(
+                    storage,
+                    buyer,
+                    vec![Coin {
+                        amount: Uint128::from(100u16),
+                        denom: "silver".to_owned(),
+                    }],
+                )
+                .expect("Failed to init bank balances");
+        }
+    });
+    let creator_addr = Addr::unchecked("creator");
+    let start_time = mock_app.block_info().time;
     let (_, addr_manager) = instantiate_collection_manager(
         &mut mock_app,
         PaymentParams {
-            beneficiary: beneficiary_addr.to_owned(),
-            mint_price: None,
+            pricing: PricingRule::DutchAuction(DutchAuction {
+                start_price: Coin {
+                    amount: Uint128::from(100u16),
+                    denom: "silver".to_owned(),
+                },
+                floor_amount: Uint128::from(40u16),
+                start_time,
+                decay_step: Uint128::from(20u16),
+                decay_interval: 60,
+                rebate: true,
+            }),
+            ..payment_params(creator_addr.to_owned(), vec![])
         },
     );
-    let new_payment_params = PaymentParams {
-        beneficiary: beneficiary_addr.to_owned(),
-        mint_price: Some(Coin {
-            denom: "silver".to_owned(),
-            amount: Uint128::from(23u16),
-        }),
+    let (_, addr_collection) = instantiate_nameservice(&mut mock_app, addr_manager.to_string());
+    register_collection(&mut mock_app, &addr_manager, &addr_collection);
+    let mint_through = |mock_app: &mut App, buyer: &Addr, name: &str, amount: u16| {
+        mock_app
+            .execute_contract(
+                buyer.to_owned(),
+                addr_manager.clone(),
+                &ExecuteMsg::PassThrough {
+                    collection: addr_collection.to_string(),
+                    message: CollectionExecuteMsg::Mint {
+                        token_id: name.to_owned(),
+                        owner: buyer.to_string(),
+                        token_uri: None,
+                        extension: None,
+                    },
+                    options: None,
+                },
+                &[Coin {
+                    amount: Uint128::from(amount),
+                    denom: "silver".to_owned(),
+                }],
+            )
+            .expect("Failed to mint through");
     };
-    let update_sudo_msg = SudoMsg::UpdatePaymentParams(new_payment_params.to_owned());
-    let sudo_msg = cw_multi_test::SudoMsg::Wasm(
-        WasmSudo::new(&addr_manager, &update_sudo_msg).expect("Failed to serialize sudo message"),
-    );
-
-    // Act
-    let result = mock_app.sudo(sudo_msg);
-
-    // Assert
+    mint_through(&mut mock_app, &early_addr, "alice", 100);
+    mock_app.update_block(|block| block.time = block.time.plus_seconds(120));
+    mint_through(&mut mock_app, &late_addr, "bob", 60);
+    let balance = |mock_app: &App, address: &Addr| {
+        mock_app
+            .wrap()
+            .query_balance(address, "silver")
+            .expect("Failed to get balance")
+            .amount
+    };
+    assert_eq!(balance(&mock_app, &creator_addr), Uint128::zero());
+    let result = mock_app.execute_contract(
+        early_addr.to_owned(),
+        addr_manager.clone(),
+        &ExecuteMsg::ClaimRebate {
+            collection: addr_collection.to_string(),
+        },
+        &[],
+    );
+    assert!(result.is_err(), "Should not claim before settlement");
+
+    // Act
+    let result = mock_app
+        .execute_contract(
+            Addr::unchecked("deployer-manager"),
+            addr_manager.clone(),
+            &ExecuteMsg::SettleAuction {
+                collection: addr_collection.to_string(),
+            },
+            &[],
+        )
+        .expect("Failed to settle auction");
+
+    // Assert
+    let expected_event = Event::new("wasm-my-collection-manager")
+        .add_attribute("settle-auction-collection", addr_collection.to_string())
+        .add_attribute("settle-auction-denom", "silver")
+        .add_attribute("settle-auction-clearing-price", "60")
+        .add_attribute("settle-auction-proceeds", "120");
+    result.assert_event(&expected_event);
+    assert_eq!(balance(&mock_app, &creator_addr), Uint128::from(120u16));
+    let _ = mock_app
+        .execute_contract(
+            early_addr.to_owned(),
+            addr_manager.clone(),
+            &ExecuteMsg::ClaimRebate {
+                collection: addr_collection.to_string(),
+            },
+            &[],
+        )
+        .expect("Failed to claim rebate");
+    assert_eq!(balance(&mock_app, &early_addr), Uint128::from(40u16));
+    let result = mock_app.execute_contract(
+        late_addr.to_owned(),
+        addr_manager.clone(),
+        &ExecuteMsg::ClaimRebate {
+            collection: addr_collection.to_string(),
+        },
+        &[],
+    );
+    assert!(
+        result.is_err(),
+        "Should have nothing to claim at the clearing price"
+    );
+    assert_eq!(balance(&mock_app, &addr_manager), Uint128::zero());
+}
+
+#[test]
+fn test_dutch_auction_rebate_twice() {
+    // Arrange
+    let buyer_addr = Addr::unchecked("buyer");
+    let mut mock_app = AppBuilder::default().build(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(
+                storage,
+                &buyer_addr,
+                vec![Coin {
+                    amount: Uint128::from(300u16),
+                    denom: "silver".to_owned(),
+                }],
+            )
+            .expect("Failed to init bank balances");
+    });
+    let creator_addr = Addr::unchecked("creator");
+    let dutch_auction = |start_time: Timestamp| DutchAuction {
+        start_price: Coin {
+            amount: Uint128::from(100u16),
+            denom: "silver".to_owned(),
+        },
+        floor_amount: Uint128::from(40u16),
+        start_time,
+        decay_step: Uint128::from(20u16),
+        decay_interval: 60,
+        rebate: true,
+    };
+    let auction_params = |start_time: Timestamp| PaymentParams {
+        pricing: PricingRule::DutchAuction(dutch_auction(start_time)),
+        ..payment_params(creator_addr.to_owned(), vec![])
+    };
+    let first_start = mock_app.block_info().time;
+    let (_, addr_manager) =
+        instantiate_collection_manager(&mut mock_app, auction_params(first_start));
+    let (_, addr_collection) = instantiate_nameservice(&mut mock_app, addr_manager.to_string());
+    register_collection(&mut mock_app, &addr_manager, &addr_collection);
+    let mint_through = |mock_app: &mut App, name: &str, amount: u16| {
+        mock_app
+            .execute_contract(
+                buyer_addr.to_owned(),
+                addr_manager.clone(),
+                &ExecuteMsg::PassThrough {
+                    collection: addr_collection.to_string(),
+                    message: CollectionExecuteMsg::Mint {
+                        token_id: name.to_owned(),
+                        owner: buyer_addr.to_string(),
+                        token_uri: None,
+                        extension: None,
+                    },
+                    options: None,
+                },
+                &[Coin {
+                    amount: Uint128::from(amount),
+                    denom: "silver".to_owned(),
+                }],
+            )
+            .expect("Failed to mint through");
+    };
+    let settle = |mock_app: &mut App| {
+        mock_app.execute_contract(
+            Addr::unchecked("deployer-manager"),
+            addr_manager.clone(),
+            &ExecuteMsg::SettleAuction {
+                collection: addr_collection.to_string(),
+            },
+            &[],
+        )
+    };
+    let claim = |mock_app: &mut App| {
+        mock_app.execute_contract(
+            buyer_addr.to_owned(),
+            addr_manager.clone(),
+            &ExecuteMsg::ClaimRebate {
+                collection: addr_collection.to_string(),
+            },
+            &[],
+        )
+    };
+    mint_through(&mut mock_app, "alice", 100);
+    mock_app.update_block(|block| block.time = block.time.plus_seconds(60));
+    mint_through(&mut mock_app, "bob", 80);
+    let error = mock_app
+        .execute_contract(
+            Addr::unchecked("deployer-manager"),
+            addr_manager.clone(),
+            &ExecuteMsg::UpdatePaymentParams(PaymentParams {
+                pricing: PricingRule::DutchAuction(DutchAuction {
+                    floor_amount: Uint128::from(20u16),
+                    ..dutch_auction(first_start)
+                }),
+                ..payment_params(creator_addr.to_owned(), vec![])
+            }),
+            &[],
+        )
+        .expect_err("Should not reconfigure the open auction");
+    assert_eq!(
+        error.root_cause().to_string(),
+        format!(
+            "auction of collection {} must be settled before it is reconfigured",
+            addr_collection
+        )
+    );
+    let error = claim(&mut mock_app).expect_err("Should not claim before the settlement");
+    assert_eq!(
+        error.root_cause().to_string(),
+        format!("auction of collection {} is not settled", addr_collection)
+    );
+    settle(&mut mock_app).expect("Failed to settle the first auction");
+    let second_start = mock_app.block_info().time;
+    let _ = mock_app
+        .execute_contract(
+            Addr::unchecked("deployer-manager"),
+            addr_manager.clone(),
+            &ExecuteMsg::UpdatePaymentParams(auction_params(second_start)),
+            &[],
+        )
+        .expect("Failed to start the second auction");
+
+    // Act
+    mint_through(&mut mock_app, "carol", 100);
+    let result = settle(&mut mock_app).expect("Failed to settle the second auction");
+
+    // Assert
+    let expected_event = Event::new("wasm-my-collection-manager")
+        .add_attribute("settle-auction-start", second_start.seconds().to_string())
+        .add_attribute("settle-auction-clearing-price", "100")
+        .add_attribute("settle-auction-proceeds", "100");
+    result.assert_event(&expected_event);
+    let error = settle(&mut mock_app).expect_err("Should have no auction left to settle");
+    assert_eq!(
+        error.root_cause().to_string(),
+        format!("auction of collection {} is settled", addr_collection)
+    );
+    let _ = claim(&mut mock_app).expect("Failed to claim the rebate of the first auction");
+    let balance = |mock_app: &App, address: &Addr| {
+        mock_app
+            .wrap()
+            .query_balance(address, "silver")
+            .expect("Failed to get balance")
+            .amount
+    };
+    assert_eq!(balance(&mock_app, &buyer_addr), Uint128::from(40u16));
+    assert_eq!(balance(&mock_app, &creator_addr), Uint128::from(260u16));
+    assert_eq!(balance(&mock_app, &addr_manager), Uint128::zero());
+}
+
+#[test]
+fn test_bonding_curve_mint_through() {
+    // Arrange
+    let sender_addr = Addr::unchecked("sender");
+    let mut mock_app = AppBuilder::default().build(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(
+                storage,
+                &sender_addr,
+                vec![Coin {
+                    amount: Uint128::from(100u16),
+                    denom: "silver".to_owned(),
+                }],
+            )
+            .expect("Failed to init bank balances");
+    });
+    let (_, addr_manager) = instantiate_collection_manager(
+        &mut mock_app,
+        PaymentParams {
+            pricing: PricingRule::BondingCurve(BondingCurve {
+                denom: "silver".to_owned(),
+                curve: Curve::Piecewise {
+                    pieces: vec![
+                        CurvePiece {
+                            from_supply: 0,
+                            price: Uint128::from(10u16),
+                        },
+                        CurvePiece {
+                            from_supply: 2,
+                            price: Uint128::from(20u16),
+                        },
+                    ],
+                },
+            }),
+            ..payment_params(Addr::unchecked("creator"), vec![])
+        },
+    );
+    let (_, addr_collection) = instantiate_nameservice(&mut mock_app, addr_manager.to_string());
+    register_collection(&mut mock_app, &addr_manager, &addr_collection);
+    let mint_through = |mock_app: &mut App, name: &str, amount: u16| {
+        mock_app.execute_contract(
+            sender_addr.to_owned(),
+            addr_manager.clone(),
+            &ExecuteMsg::PassThrough {
+                collection: addr_collection.to_string(),
+                message: CollectionExecuteMsg::Mint {
+                    token_id: name.to_owned(),
+                    owner: "owner".to_owned(),
+                    token_uri: None,
+                    extension: None,
+                },
+                options: None,
+            },
+            &[Coin {
+                amount: Uint128::from(amount),
+                denom: "silver".to_owned(),
+            }],
+        )
+    };
+    let _ = mint_through(&mut mock_app, "alice", 10).expect("Failed to mint the first name");
+    let quote = mock_app
+        .wrap()
+        .query_wasm_smart::<QuoteMintResponse>(
+            &addr_manager,
+            &QueryMsg::QuoteMint {
+                collection: addr_collection.to_string(),
+                quantity: 2,
+            },
+        )
+        .expect("Failed to quote mints");
+    assert_eq!(
+        quote.totals,
+        vec![Coin {
+            amount: Uint128::from(30u16),
+            denom: "silver".to_owned(),
+        }]
+    );
+    let _ = mint_through(&mut mock_app, "bob", 10).expect("Failed to mint the second name");
+
+    // Act
+    let result = mint_through(&mut mock_app, "carol", 10);
+
+    // Assert
+    assert!(result.is_err(), "Should have charged the next piece");
+    let _ = mint_through(&mut mock_app, "carol", 20).expect("Failed to mint the third name");
+    assert_eq!(
+        mock_app
+            .wrap()
+            .query_balance("creator", "silver")
+            .expect("Failed to get creator balance")
+            .amount,
+        Uint128::from(40u16)
+    );
+}
+
+#[test]
+fn test_quote_name() {
+    // Arrange
+    let mut mock_app = App::default();
+    let (_, addr_manager) = instantiate_collection_manager(
+        &mut mock_app,
+        PaymentParams {
+            pricing: PricingRule::NameLength(NameLengthPricing {
+                denom: "silver".to_owned(),
+                tiers: vec![
+                    LengthTier {
+                        min_length: 1,
+                        price: Uint128::from(100u16),
+                    },
+                    LengthTier {
+                        min_length: 4,
+                        price: Uint128::from(10u16),
+                    },
+                ],
+                premium_names: vec![PremiumName {
+                    name: "alice".to_owned(),
+                    price: Uint128::from(500u16),
+                }],
+            }),
+            ..payment_params(Addr::unchecked("beneficiary"), vec![])
+        },
+    );
+    let (_, addr_collection) = instantiate_nameservice(&mut mock_app, addr_manager.to_string());
+    register_collection(&mut mock_app, &addr_manager, &addr_collection);
+    let quote_name = |mock_app: &App, name: &str| {
+        mock_app
+            .wrap()
+            .query_wasm_smart::<QuoteNameResponse>(
+                &addr_manager,
+                &QueryMsg::QuoteName {
+                    collection: addr_collection.to_string(),
+                    name: name.to_owned(),
+                },
+            )
+            .expect("Failed to quote name")
+    };
+    let silver = |amount: u16| Coin {
+        amount: Uint128::from(amount),
+        denom: "silver".to_owned(),
+    };
+
+    // Act
+    let quotes = ["été", "éclat", "alice"].map(|name| quote_name(&mock_app, name));
+
+    // Assert
+    assert_eq!(
+        quotes,
+        [
+            QuoteNameResponse {
+                mint_prices: vec![silver(100)],
+                premium: false,
+            },
+            QuoteNameResponse {
+                mint_prices: vec![silver(10)],
+                premium: false,
+            },
+            QuoteNameResponse {
+                mint_prices: vec![silver(500)],
+                premium: true,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_renew_name() {
+    // Arrange
+    let sender_addr = Addr::unchecked("sender");
+    let mut mock_app = AppBuilder::default().build(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(
+                storage,
+                &sender_addr,
+                vec![Coin {
+                    amount: Uint128::from(100u16),
+                    denom: "silver".to_owned(),
+                }],
+            )
+            .expect("Failed to init bank balances");
+    });
+    let yearly_price = Coin {
+        amount: Uint128::from(10u16),
+        denom: "silver".to_owned(),
+    };
+    let (_, addr_manager) = instantiate_collection_manager(
+        &mut mock_app,
+        PaymentParams {
+            subscription: Some(Subscription {
+                max_years: 5,
+                grace_period: 86_400,
+            }),
+            ..payment_params(Addr::unchecked("creator"), vec![yearly_price.to_owned()])
+        },
+    );
+    let (_, addr_collection) = instantiate_nameservice(&mut mock_app, addr_manager.to_string());
+    register_collection(&mut mock_app, &addr_manager, &addr_collection);
+    let minted_at = mock_app.block_info().time;
+    let _ = mock_app
+        .execute_contract(
+            sender_addr.to_owned(),
+            addr_manager.clone(),
+            &ExecuteMsg::PassThrough {
+                collection: addr_collection.to_string(),
+                message: CollectionExecuteMsg::Mint {
+                    token_id: "alice".to_owned(),
+                    owner: sender_addr.to_string(),
+                    token_uri: None,
+                    extension: None,
+                },
+                options: None,
+            },
+            &[yearly_price.to_owned()],
+        )
+        .expect("Failed to mint through");
+    let renew_msg = ExecuteMsg::Renew {
+        collection: addr_collection.to_string(),
+        token_id: "alice".to_owned(),
+        years: 2,
+    };
+    let result = mock_app.execute_contract(
+        sender_addr.to_owned(),
+        addr_manager.clone(),
+        &renew_msg,
+        &[yearly_price.to_owned()],
+    );
+    assert!(result.is_err(), "Should charge each year renewed");
+
+    // Act
+    let result = mock_app
+        .execute_contract(
+            sender_addr.to_owned(),
+            addr_manager.clone(),
+            &renew_msg,
+            &[Coin {
+                amount: Uint128::from(25u16),
+                denom: "silver".to_owned(),
+            }],
+        )
+        .expect("Failed to renew");
+
+    // Assert
+    let expires_at = minted_at.plus_seconds(3 * SECONDS_PER_YEAR);
+    let expected_event = Event::new("wasm-my-collection-manager")
+        .add_attribute("renew-collection", addr_collection.to_string())
+        .add_attribute("renew-name", "alice")
+        .add_attribute("renew-expires-at", expires_at.seconds().to_string())
+        .add_attribute("renew-payment-denom", "silver")
+        .add_attribute("renew-payment-amount", "20");
+    result.assert_event(&expected_event);
+    let expiry = mock_app
+        .wrap()
+        .query_wasm_smart::<NameExpiryResponse>(
+            &addr_manager,
+            &QueryMsg::NameExpiry {
+                collection: addr_collection.to_string(),
+                token_id: "alice".to_owned(),
+            },
+        )
+        .expect("Failed to query name expiry");
+    assert_eq!(
+        expiry,
+        NameExpiryResponse {
+            expires_at: Some(expires_at),
+            grace_ends_at: Some(expires_at.plus_seconds(86_400)),
+        }
+    );
+    assert_eq!(
+        mock_app
+            .wrap()
+            .query_balance(&sender_addr, "silver")
+            .expect("Failed to get sender balance")
+            .amount,
+        Uint128::from(70u16)
+    );
+}
+
+#[test]
+fn test_reclaim_expired_name() {
+    // Arrange
+    let sender_addr = Addr::unchecked("sender");
+    let mut mock_app = AppBuilder::default().build(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(
+                storage,
+                &sender_addr,
+                vec![Coin {
+                    amount: Uint128::from(100u16),
+                    denom: "silver".to_owned(),
+                }],
+            )
+            .expect("Failed to init bank balances");
+    });
+    let yearly_price = Coin {
+        amount: Uint128::from(10u16),
+        denom: "silver".to_owned(),
+    };
+    let (_, addr_manager) = instantiate_collection_manager(
+        &mut mock_app,
+        PaymentParams {
+            subscription: Some(Subscription {
+                max_years: 5,
+                grace_period: 86_400,
+            }),
+            ..payment_params(Addr::unchecked("creator"), vec![yearly_price.to_owned()])
+        },
+    );
+    let (_, addr_collection) = instantiate_nameservice(&mut mock_app, addr_manager.to_string());
+    register_collection(&mut mock_app, &addr_manager, &addr_collection);
+    for name in ["alice", "bob"] {
+        let _ = mock_app
+            .execute_contract(
+                sender_addr.to_owned(),
+                addr_manager.clone(),
+                &ExecuteMsg::PassThrough {
+                    collection: addr_collection.to_string(),
+                    message: CollectionExecuteMsg::Mint {
+                        token_id: name.to_owned(),
+                        owner: sender_addr.to_string(),
+                        token_uri: None,
+                        extension: None,
+                    },
+                    options: None,
+                },
+                &[yearly_price.to_owned()],
+            )
+            .expect("Failed to mint through");
+    }
+    let renew_msg = |name: &str| ExecuteMsg::Renew {
+        collection: addr_collection.to_string(),
+        token_id: name.to_owned(),
+        years: 1,
+    };
+    let _ = mock_app
+        .execute_contract(
+            Addr::unchecked("deployer-manager"),
+            addr_manager.clone(),
+            &ExecuteMsg::Pause {
+                collection: Some(addr_collection.to_string()),
+                until_height: None,
+            },
+            &[],
+        )
+        .expect("Failed to pause");
+    let error = mock_app
+        .execute_contract(
+            sender_addr.to_owned(),
+            addr_manager.clone(),
+            &renew_msg("alice"),
+            &[yearly_price.to_owned()],
+        )
+        .expect_err("Should not renew while paused");
+    assert_eq!(
+        error.root_cause().to_string(),
+        format!(
+            "minting and renewing on collection {} is paused",
+            addr_collection
+        )
+    );
+    mock_app.update_block(|block| {
+        block.time = block.time.plus_seconds(SECONDS_PER_YEAR + 86_401);
+    });
+    let reclaim_msg = ExecuteMsg::ReclaimExpired {
+        collection: addr_collection.to_string(),
+        token_id: "alice".to_owned(),
+    };
+    let error = mock_app
+        .execute_contract(
+            Addr::unchecked("deployer-manager"),
+            addr_manager.clone(),
+            &reclaim_msg,
+            &[],
+        )
+        .expect_err("Should not reclaim without approval");
+    assert_eq!(
+        error.root_cause().to_string(),
+        "name alice can only be reclaimed once its owner approved the manager"
+    );
+    let error = mock_app
+        .execute_contract(
+            Addr::unchecked("deployer-manager"),
+            addr_manager.clone(),
+            &ExecuteMsg::ReclaimExpired {
+                collection: addr_manager.to_string(),
+                token_id: "alice".to_owned(),
+            },
+            &[],
+        )
+        .expect_err("Should not reclaim on an unregistered collection");
+    assert_eq!(
+        error.root_cause().to_string(),
+        format!("collection {} is not registered", addr_manager)
+    );
+    let _ = mock_app
+        .execute_contract(
+            Addr::unchecked("deployer-manager"),
+            addr_manager.clone(),
+            &ExecuteMsg::Unpause {
+                collection: Some(addr_collection.to_string()),
+            },
+            &[],
+        )
+        .expect("Failed to unpause");
+    let _ = mock_app
+        .execute_contract(
+            sender_addr.to_owned(),
+            addr_manager.clone(),
+            &renew_msg("alice"),
+            &[yearly_price.to_owned()],
+        )
+        .expect("Failed to renew after the grace period");
+    let expiry = mock_app
+        .wrap()
+        .query_wasm_smart::<NameExpiryResponse>(
+            &addr_manager,
+            &QueryMsg::NameExpiry {
+                collection: addr_collection.to_string(),
+                token_id: "alice".to_owned(),
+            },
+        )
+        .expect("Failed to query name expiry");
+    assert_eq!(
+        expiry.expires_at,
+        Some(mock_app.block_info().time.plus_seconds(SECONDS_PER_YEAR))
+    );
+    let _ = mock_app
+        .execute_contract(
+            sender_addr.to_owned(),
+            addr_collection.clone(),
+            &CollectionExecuteMsg::Burn {
+                token_id: "bob".to_owned(),
+            },
+            &[],
+        )
+        .expect("Failed to burn on the collection");
+    let error = mock_app
+        .execute_contract(
+            sender_addr.to_owned(),
+            addr_manager.clone(),
+            &renew_msg("bob"),
+            &[yearly_price.to_owned()],
+        )
+        .expect_err("Should not renew a burnt name");
+    assert_eq!(
+        error.root_cause().to_string(),
+        "name bob is not registered with a subscription"
+    );
+    mock_app.update_block(|block| {
+        block.time = block.time.plus_seconds(SECONDS_PER_YEAR + 86_401);
+    });
+    let _ = mock_app
+        .execute_contract(
+            sender_addr.to_owned(),
+            addr_collection.clone(),
+            &CollectionExecuteMsg::ApproveAll {
+                operator: addr_manager.to_string(),
+                expires: None,
+            },
+            &[],
+        )
+        .expect("Failed to approve the manager");
+
+    // Act
+    let result = mock_app
+        .execute_contract(
+            Addr::unchecked("deployer-manager"),
+            addr_manager.clone(),
+            &reclaim_msg,
+            &[],
+        )
+        .expect("Failed to reclaim");
+
+    // Assert
+    let expected_event = Event::new("wasm")
+        .add_attribute("action", "burn")
+        .add_attribute("token_id", "alice");
+    result.assert_event(&expected_event);
+    let owner_result = mock_app.wrap().query_wasm_smart::<OwnerOfResponse>(
+        &addr_collection,
+        &CollectionQueryMsg::OwnerOf {
+            token_id: "alice".to_owned(),
+            include_expired: None,
+        },
+    );
+    assert!(owner_result.is_err(), "Should have burnt the name");
+    let expiry = mock_app
+        .wrap()
+        .query_wasm_smart::<NameExpiryResponse>(
+            &addr_manager,
+            &QueryMsg::NameExpiry {
+                collection: addr_collection.to_string(),
+                token_id: "alice".to_owned(),
+            },
+        )
+        .expect("Failed to query name expiry");
+    assert_eq!(
+        expiry,
+        NameExpiryResponse {
+            expires_at: None,
+            grace_ends_at: None,
+        }
+    );
+}
+
+#[test]
+fn test_atomic_batch_mint_through() {
+    // Arrange
+    let sender_addr = Addr::unchecked("sender");
+    let mut mock_app = AppBuilder::default().build(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(
+                storage,
+                &sender_addr,
+                vec![Coin {
+                    amount: Uint128::from(100u16),
+                    denom: "silver".to_owned(),
+                }],
+            )
+            .expect("Failed to init bank balances");
+    });
+    let minting_price = Coin {
+        amount: Uint128::from(10u16),
+        denom: "silver".to_owned(),
+    };
+    let (_, addr_manager) = instantiate_collection_manager(
+        &mut mock_app,
+        payment_params(Addr::unchecked("creator"), vec![minting_price.to_owned()]),
+    );
+    let (_, addr_collection) = instantiate_nameservice(&mut mock_app, addr_manager.to_string());
+    register_collection(&mut mock_app, &addr_manager, &addr_collection);
+    let mint_item = |name: &str| PassThroughItem {
+        collection: addr_collection.to_string(),
+        message: CollectionExecuteMsg::Mint {
+            token_id: name.to_owned(),
+            owner: sender_addr.to_string(),
+            token_uri: None,
+            extension: None,
+        },
+        options: None,
+    };
+    let batch_msg = |names: &[&str]| ExecuteMsg::PassThroughBatch {
+        items: names.iter().map(|name| mint_item(name)).collect(),
+        mode: BatchMode::Atomic,
+    };
+    let balance = |mock_app: &App| {
+        mock_app
+            .wrap()
+            .query_balance(&sender_addr, "silver")
+            .expect("Failed to get sender balance")
+            .amount
+    };
+
+    // Act
+    let result = mock_app
+        .execute_contract(
+            sender_addr.to_owned(),
+            addr_manager.clone(),
+            &batch_msg(&["alice", "bob"]),
+            &[Coin {
+                amount: Uint128::from(25u16),
+                denom: "silver".to_owned(),
+            }],
+        )
+        .expect("Failed to mint the batch");
+
+    // Assert
+    let expected_event = Event::new("wasm-my-collection-manager")
+        .add_attribute("batch-item", "1")
+        .add_attribute("batch-item-collection", addr_collection.to_string())
+        .add_attribute("token-count-before", "1");
+    result.assert_event(&expected_event);
+    assert_eq!(balance(&mock_app), Uint128::from(80u16));
+    let result = mock_app.execute_contract(
+        sender_addr.to_owned(),
+        addr_manager.clone(),
+        &batch_msg(&["carol", "alice"]),
+        &[Coin {
+            amount: Uint128::from(20u16),
+            denom: "silver".to_owned(),
+        }],
+    );
+    assert!(result.is_err(), "Should have failed on the taken name");
+    assert_eq!(balance(&mock_app), Uint128::from(80u16));
+    let carol_owner = mock_app.wrap().query_wasm_smart::<OwnerOfResponse>(
+        &addr_collection,
+        &CollectionQueryMsg::OwnerOf {
+            token_id: "carol".to_owned(),
+            include_expired: None,
+        },
+    );
+    assert!(carol_owner.is_err(), "Should have reverted the whole batch");
+}
+
+#[test]
+fn test_best_effort_batch_mint_through() {
+    // Arrange
+    let sender_addr = Addr::unchecked("sender");
+    let mut mock_app = AppBuilder::default().build(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(
+                storage,
+                &sender_addr,
+                vec![Coin {
+                    amount: Uint128::from(100u16),
+                    denom: "silver".to_owned(),
+                }],
+            )
+            .expect("Failed to init bank balances");
+    });
+    let (_, addr_manager) = instantiate_collection_manager(
+        &mut mock_app,
+        payment_params(
+            Addr::unchecked("creator"),
+            vec![Coin {
+                amount: Uint128::from(10u16),
+                denom: "silver".to_owned(),
+            }],
+        ),
+    );
+    let (_, addr_collection) = instantiate_nameservice(&mut mock_app, addr_manager.to_string());
+    register_collection(&mut mock_app, &addr_manager, &addr_collection);
+    let mint_item = |name: &str| PassThroughItem {
+        collection: addr_collection.to_string(),
+        message: CollectionExecuteMsg::Mint {
+            token_id: name.to_owned(),
+            owner: sender_addr.to_string(),
+            token_uri: None,
+            extension: None,
+        },
+        options: None,
+    };
+    let batch_msg = ExecuteMsg::PassThroughBatch {
+        items: vec![mint_item("alice"), mint_item("alice"), mint_item("bob")],
+        mode: BatchMode::BestEffort,
+    };
+    let balance = |mock_app: &App, address: &str| {
+        mock_app
+            .wrap()
+            .query_balance(address, "silver")
+            .expect("Failed to get balance")
+            .amount
+    };
+
+    // Act
+    let result = mock_app
+        .execute_contract(
+            sender_addr.to_owned(),
+            addr_manager.clone(),
+            &batch_msg,
+            &[Coin {
+                amount: Uint128::from(30u16),
+                denom: "silver".to_owned(),
+            }],
+        )
+        .expect("Failed to mint the batch");
+
+    // Assert
+    let expected_event = Event::new("wasm-my-collection-manager")
+        .add_attribute("batch-item", "1")
+        .add_attribute("batch-item-collection", addr_collection.to_string())
+        .add_attribute("batch-item-status", "failed");
+    result.assert_event(&expected_event);
+    let batch_response = from_json::<BatchResponse>(result.data.expect("Should return the result"))
+        .expect("Failed to parse the batch result");
+    assert_eq!(batch_response.succeeded, vec![0, 2]);
+    assert_eq!(batch_response.failed.len(), 1);
+    assert_eq!(batch_response.failed[0].index, 1);
+    assert!(!batch_response.failed[0].error.is_empty());
+    assert_eq!(
+        balance(&mock_app, sender_addr.as_str()),
+        Uint128::from(80u16)
+    );
+    assert_eq!(balance(&mock_app, "creator"), Uint128::from(20u16));
+    assert_eq!(balance(&mock_app, addr_manager.as_str()), Uint128::zero());
+    let bob_owner = mock_app
+        .wrap()
+        .query_wasm_smart::<OwnerOfResponse>(
+            &addr_collection,
+            &CollectionQueryMsg::OwnerOf {
+                token_id: "bob".to_owned(),
+                include_expired: None,
+            },
+        )
+        .expect("Failed to query bob");
+    assert_eq!(bob_owner.owner, sender_addr.to_string());
+}
+
+#[test]
+fn test_mint_through_catching_errors() {
+    // Arrange
+    let sender_addr = Addr::unchecked("sender");
+    let mut mock_app = AppBuilder::default().build(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(
+                storage,
+                &sender_addr,
+                vec![Coin {
+                    amount: Uint128::from(100u16),
+                    denom: "silver".to_owned(),
+                }],
+            )
+            .expect("Failed to init bank balances");
+    });
+    let minting_price = Coin {
+        amount: Uint128::from(10u16),
+        denom: "silver".to_owned(),
+    };
+    let (_, addr_manager) = instantiate_collection_manager(
+        &mut mock_app,
+        payment_params(Addr::unchecked("creator"), vec![minting_price.to_owned()]),
+    );
+    let (_, addr_collection) = instantiate_nameservice(&mut mock_app, addr_manager.to_string());
+    register_collection(&mut mock_app, &addr_manager, &addr_collection);
+    let mint_msg = ExecuteMsg::PassThrough {
+        collection: addr_collection.to_string(),
+        message: CollectionExecuteMsg::Mint {
+            token_id: "alice".to_owned(),
+            owner: sender_addr.to_string(),
+            token_uri: None,
+            extension: None,
+        },
+        options: Some(PassThroughOptions {
+            allowlist: None,
+            years: None,
+            catch_errors: true,
+            return_data: None,
+        }),
+    };
+    let balance = |mock_app: &App, address: &str| {
+        mock_app
+            .wrap()
+            .query_balance(address, "silver")
+            .expect("Failed to get balance")
+            .amount
+    };
+    let result = mock_app
+        .execute_contract(
+            sender_addr.to_owned(),
+            addr_manager.clone(),
+            &mint_msg,
+            &[minting_price.to_owned()],
+        )
+        .expect("Failed to mint alice");
+    assert_eq!(result.data, None);
+    assert_eq!(balance(&mock_app, "creator"), Uint128::from(10u16));
+
+    // Act
+    let result = mock_app
+        .execute_contract(
+            sender_addr.to_owned(),
+            addr_manager.clone(),
+            &mint_msg,
+            &[minting_price],
+        )
+        .expect("Should have caught the taken name");
+
+    // Assert
+    let expected_event = Event::new("wasm-my-collection-manager")
+        .add_attribute(
+            "pass-through-failed-collection",
+            addr_collection.to_string(),
+        )
+        .add_attribute("pass-through-failed-kind", "mint");
+    result.assert_event(&expected_event);
+    let failure = from_json::<PassThroughFailure>(result.data.expect("Should explain the failure"))
+        .expect("Failed to parse the failure");
+    assert_eq!(failure.collection, addr_collection);
+    assert_eq!(failure.kind, CollectionMsgKind::Mint);
+    assert!(!failure.error.is_empty());
+    assert_eq!(
+        balance(&mock_app, sender_addr.as_str()),
+        Uint128::from(90u16)
+    );
+    assert_eq!(balance(&mock_app, "creator"), Uint128::from(10u16));
+    assert_eq!(balance(&mock_app, addr_manager.as_str()), Uint128::zero());
+}
+
+#[test]
+fn test_mint_through_returning_data() {
+    // Arrange
+    let sender_addr = Addr::unchecked("sender");
+    let mut mock_app = App::default();
+    let (_, addr_manager) = instantiate_collection_manager(
+        &mut mock_app,
+        payment_params(Addr::unchecked("beneficiary"), vec![]),
+    );
+    let (_, addr_collection) = instantiate_nameservice(&mut mock_app, addr_manager.to_string());
+    register_collection(&mut mock_app, &addr_manager, &addr_collection);
+    let mint_msg = |name: &str, return_data: DataFormat| ExecuteMsg::PassThrough {
+        collection: addr_collection.to_string(),
+        message: CollectionExecuteMsg::Mint {
+            token_id: name.to_owned(),
+            owner: sender_addr.to_string(),
+            token_uri: None,
+            extension: None,
+        },
+        options: Some(PassThroughOptions {
+            allowlist: None,
+            years: None,
+            catch_errors: false,
+            return_data: Some(return_data),
+        }),
+    };
+
+    // Act
+    let raw_result = mock_app
+        .execute_contract(
+            sender_addr.to_owned(),
+            addr_manager.clone(),
+            &mint_msg("alice", DataFormat::Raw),
+            &[],
+        )
+        .expect("Failed to mint alice");
+    let envelope_result = mock_app
+        .execute_contract(
+            sender_addr.to_owned(),
+            addr_manager.clone(),
+            &mint_msg("bob", DataFormat::Envelope),
+            &[],
+        )
+        .expect("Failed to mint bob");
+
+    // Assert
+    let raw_data = raw_result.data.expect("Should return the collection data");
+    assert_eq!(
+        from_json::<NameServiceExecuteMsgResponse>(&raw_data)
+            .expect("Failed to parse the collection data")
+            .num_tokens,
+        1
+    );
+    let envelope =
+        from_json::<PassThroughData>(envelope_result.data.expect("Should return the envelope"))
+            .expect("Failed to parse the envelope");
+    assert_eq!(envelope.collection, addr_collection);
+    assert_eq!(envelope.token_count_before, 1);
+    assert_eq!(envelope.token_count_after, Some(2));
+    assert!(envelope.data.is_some());
+}
+
+#[test]
+fn test_cw20_paid_mint_through() {
+    // Arrange
+    let mut mock_app = App::default();
+    let sender_addr = Addr::unchecked("sender");
+    let creator_addr = Addr::unchecked("creator");
+    let cw20_code_id = mock_app.store_code(Box::new(ContractWrapper::new(
+        execute_cw20,
+        instantiate_cw20,
+        query_cw20,
+    )));
+    let addr_token = mock_app
+        .instantiate_contract(
+            cw20_code_id,
+            Addr::unchecked("deployer-token"),
+            &Cw20InstantiateMsg {
+                name: "Community token".to_owned(),
+                symbol: "COMM".to_owned(),
+                decimals: 6,
+                initial_balances: vec![Cw20Coin {
+                    address: sender_addr.to_string(),
+                    amount: Uint128::from(100u16),
+                }],
+                mint: None,
+                marketing: None,
+            },
+            &[],
+            "community-token",
+            None,
+        )
+        .expect("Failed to instantiate token");
+    let (_, addr_manager) = instantiate_collection_manager(
+        &mut mock_app,
+        PaymentParams {
+            cw20_mint_price: Some(Cw20CoinVerified {
+                address: addr_token.to_owned(),
+                amount: Uint128::from(40u16),
+            }),
+            ..payment_params(creator_addr.to_owned(), vec![])
+        },
+    );
+    let (_, addr_collection) = instantiate_nameservice(&mut mock_app, addr_manager.to_string());
+    register_collection(&mut mock_app, &addr_manager, &addr_collection);
+    let send_msg = |amount: u16| Cw20ExecuteMsg::Send {
+        contract: addr_manager.to_string(),
+        amount: Uint128::from(amount),
+        msg: to_json_binary(&ReceiveMsg::PassThrough {
+            collection: addr_collection.to_string(),
+            message: CollectionExecuteMsg::Mint {
+                token_id: "alice".to_owned(),
+                owner: "owner".to_owned(),
+                token_uri: None,
+                extension: None,
+            },
+            options: None,
+        })
+        .expect("Failed to serialize receive message"),
+    };
+    let result = mock_app.execute_contract(
+        sender_addr.to_owned(),
+        addr_token.clone(),
+        &send_msg(39),
+        &[],
+    );
+    assert!(result.is_err(), "Should not mint when underpaid");
+    let error = mock_app
+        .execute_contract(
+            sender_addr.to_owned(),
+            addr_manager.clone(),
+            &ExecuteMsg::PassThrough {
+                collection: addr_collection.to_string(),
+                message: CollectionExecuteMsg::Mint {
+                    token_id: "bob".to_owned(),
+                    owner: "owner".to_owned(),
+                    token_uri: None,
+                    extension: None,
+                },
+                options: None,
+            },
+            &[],
+        )
+        .expect_err("Should not mint for free without native prices");
+    assert_eq!(
+        error.root_cause().to_string(),
+        format!("mints are only paid in CW20 token {}", addr_token)
+    );
+
+    // Act
+    let result = mock_app
+        .execute_contract(
+            sender_addr.to_owned(),
+            addr_token.clone(),
+            &send_msg(50),
+            &[],
+        )
+        .expect("Failed to mint with tokens");
+
+    // Assert
+    let expected_manager_event = Event::new("wasm-my-collection-manager")
+        .add_attribute("token-count-before", "0")
+        .add_attribute("mint-payment-cw20", addr_token.to_string())
+        .add_attribute("mint-payment-amount", "40");
+    result.assert_event(&expected_manager_event);
+    let cw20_balance = |mock_app: &App, address: &Addr| {
+        mock_app
+            .wrap()
+            .query_wasm_smart::<BalanceResponse>(
+                &addr_token,
+                &Cw20QueryMsg::Balance {
+                    address: address.to_string(),
+                },
+            )
+            .expect("Failed to query token balance")
+            .balance
+    };
+    assert_eq!(cw20_balance(&mock_app, &creator_addr), Uint128::from(40u16));
+    assert_eq!(cw20_balance(&mock_app, &sender_addr), Uint128::from(60u16));
+    assert_eq!(cw20_balance(&mock_app, &addr_manager), Uint128::zero());
+    let owner_query = CollectionQueryMsg::OwnerOf {
+        token_id: "alice".to_owned(),
+        include_expired: None,
+    };
+    let owner = mock_app
+        .wrap()
+        .query_wasm_smart::<OwnerOfResponse>(&addr_collection, &owner_query)
+        .expect("Failed to query alice");
+    assert_eq!(owner.owner, "owner".to_owned());
+}
+
+#[test]
+fn test_current_phase() {
+    // Arrange
+    let mut mock_app = App::default();
+    let start_height = mock_app.block_info().height;
+    let presale = MintPhase {
+        name: "presale".to_owned(),
+        start: Scheduled::AtHeight(start_height),
+        end: Expiration::AtHeight(start_height + 10),
+        mint_prices: vec![],
+        cap: None,
+        max_per_wallet: None,
+        allowlist_root: None,
+    };
+    let (_, addr_manager) = instantiate_collection_manager(
+        &mut mock_app,
+        PaymentParams {
+            phases: vec![presale.to_owned()],
+            ..payment_params(Addr::unchecked("beneficiary"), vec![])
+        },
+    );
+    let (_, addr_collection) = instantiate_nameservice(&mut mock_app, addr_manager.to_string());
+    register_collection(&mut mock_app, &addr_manager, &addr_collection);
+    let _ = mock_app
+        .execute_contract(
+            Addr::unchecked("sender"),
+            addr_manager.clone(),
+            &ExecuteMsg::PassThrough {
+                collection: addr_collection.to_string(),
+                message: CollectionExecuteMsg::Mint {
+                    token_id: "alice".to_owned(),
+                    owner: "owner".to_owned(),
+                    token_uri: None,
+                    extension: None,
+                },
+                options: None,
+            },
+            &[],
+        )
+        .expect("Failed to mint during presale");
+    let phase_query = QueryMsg::CurrentPhase {
+        collection: addr_collection.to_string(),
+    };
+
+    // Act
+    let during_presale = mock_app
+        .wrap()
+        .query_wasm_smart::<CurrentPhaseResponse>(&addr_manager, &phase_query)
+        .expect("Failed to query current phase");
+    mock_app.update_block(|block| block.height += 10);
+    let after_presale = mock_app
+        .wrap()
+        .query_wasm_smart::<CurrentPhaseResponse>(&addr_manager, &phase_query)
+        .expect("Failed to query current phase");
+
+    // Assert
+    assert_eq!(
+        during_presale,
+        CurrentPhaseResponse {
+            has_phases: true,
+            phase: Some(presale),
+            minted: 1,
+        }
+    );
+    assert_eq!(
+        after_presale,
+        CurrentPhaseResponse {
+            has_phases: true,
+            phase: None,
+            minted: 0,
+        }
+    );
+}
+
+#[test]
+fn test_allowlist_eligibility() {
+    // Arrange
+    let mut mock_app = App::default();
+    let sender_addr = Addr::unchecked("sender");
+    let allowlist_root: [u8; 32] = Sha256::digest(b"sender:1").into();
+    let (_, addr_manager) = instantiate_collection_manager(
+        &mut mock_app,
+        PaymentParams {
+            phases: vec![MintPhase {
+                name: "presale".to_owned(),
+                start: Scheduled::AtHeight(0),
+                end: Expiration::Never {},
+                mint_prices: vec![],
+                cap: None,
+                max_per_wallet: None,
+                allowlist_root: Some(HexBinary::from(allowlist_root.as_slice())),
+            }],
+            ..payment_params(Addr::unchecked("beneficiary"), vec![])
+        },
+    );
+    let (_, addr_collection) = instantiate_nameservice(&mut mock_app, addr_manager.to_string());
+    register_collection(&mut mock_app, &addr_manager, &addr_collection);
+    let allowlist = AllowlistProof {
+        allocation: 1,
+        proof: vec![],
+    };
+    let eligibility_query = QueryMsg::AllowlistEligibility {
+        collection: addr_collection.to_string(),
+        address: sender_addr.to_string(),
+        allowlist: Some(allowlist.to_owned()),
+    };
+    let before_mint = mock_app
+        .wrap()
+        .query_wasm_smart::<AllowlistEligibilityResponse>(&addr_manager, &eligibility_query)
+        .expect("Failed to query eligibility");
+
+    // Act
+    let _ = mock_app
+        .execute_contract(
+            sender_addr,
+            addr_manager.clone(),
+            &ExecuteMsg::PassThrough {
+                collection: addr_collection.to_string(),
+                message: CollectionExecuteMsg::Mint {
+                    token_id: "alice".to_owned(),
+                    owner: "owner".to_owned(),
+                    token_uri: None,
+                    extension: None,
+                },
+                options: Some(PassThroughOptions {
+                    allowlist: Some(allowlist),
+                    years: None,
+                    catch_errors: false,
+                    return_data: None,
+                }),
+            },
+            &[],
+        )
+        .expect("Failed to mint with allowlist proof");
+
+    // Assert
+    assert_eq!(
+        before_mint,
+        AllowlistEligibilityResponse {
+            eligible: true,
+            phase: Some("presale".to_owned()),
+            remaining_allocation: Some(1),
+        }
+    );
+    let after_mint = mock_app
+        .wrap()
+        .query_wasm_smart::<AllowlistEligibilityResponse>(&addr_manager, &eligibility_query)
+        .expect("Failed to query eligibility");
+    assert_eq!(
+        after_mint,
+        AllowlistEligibilityResponse {
+            eligible: false,
+            phase: Some("presale".to_owned()),
+            remaining_allocation: Some(0),
+        }
+    );
+}
+
+#[test]
+fn test_phase_wallet_limit() {
+    // Arrange
+    let mut mock_app = App::default();
+    let sender_addr = Addr::unchecked("sender");
+    let (_, addr_manager) = instantiate_collection_manager(
+        &mut mock_app,
+        PaymentParams {
+            phases: vec![MintPhase {
+                name: "public".to_owned(),
+                start: Scheduled::AtHeight(0),
+                end: Expiration::Never {},
+                mint_prices: vec![],
+                cap: None,
+                max_per_wallet: Some(1),
+                allowlist_root: None,
+            }],
+            ..payment_params(Addr::unchecked("beneficiary"), vec![])
+        },
+    );
+    let (_, addr_collection) = instantiate_nameservice(&mut mock_app, addr_manager.to_string());
+    register_collection(&mut mock_app, &addr_manager, &addr_collection);
+    let mint_msg = |name: &str| ExecuteMsg::PassThrough {
+        collection: addr_collection.to_string(),
+        message: CollectionExecuteMsg::Mint {
+            token_id: name.to_owned(),
+            owner: "owner".to_owned(),
+            token_uri: None,
+            extension: None,
+        },
+        options: None,
+    };
+    let _ = mock_app
+        .execute_contract(
+            sender_addr.to_owned(),
+            addr_manager.clone(),
+            &mint_msg("alice"),
+            &[],
+        )
+        .expect("Failed to mint within the wallet limit");
+
+    // Act
+    let result = mock_app.execute_contract(
+        sender_addr.to_owned(),
+        addr_manager.clone(),
+        &mint_msg("bob"),
+        &[],
+    );
+
+    // Assert
+    assert!(
+        result.is_err(),
+        "Should have stopped at the phase wallet limit"
+    );
+    let limits = mock_app
+        .wrap()
+        .query_wasm_smart::<GetMintLimitsResponse>(
+            &addr_manager,
+            &QueryMsg::GetMintLimits {
+                collection: addr_collection.to_string(),
+                address: Some(sender_addr.to_string()),
+            },
+        )
+        .expect("Failed to query mint limits");
+    assert_eq!(
+        limits,
+        GetMintLimitsResponse {
+            limits: MintLimits::default(),
+            minted: 1,
+            minted_by_address: Some(1),
+        }
+    );
+}
+
+#[test]
+fn test_mint_num_tokens() {
+    // Arrange
+    let mut mock_app = App::default();
+    let beneficiary_addr = Addr::unchecked("beneficiary");
+    let (_, addr_manager) = instantiate_collection_manager(
+        &mut mock_app,
+        payment_params(beneficiary_addr.to_owned(), vec![]),
+    );
+    let (_, addr_collection) = instantiate_nameservice(&mut mock_app, addr_manager.to_string());
+    register_collection(&mut mock_app, &addr_manager, &addr_collection);
+    let owner_addr = Addr::unchecked("owner");
+    let name_alice = "alice".to_owned();
+    let name_bob = "bob".to_owned();
+    let sender_addr = Addr::unchecked("sender");
+    let register_msg = ExecuteMsg::PassThrough {
+        collection: addr_collection.to_string(),
+        message: CollectionExecuteMsg::Mint {
+            token_id: name_alice.clone(),
+            owner: owner_addr.to_string(),
+            token_uri: None,
+            extension: None,
+        },
+        options: None,
+    };
+    let _ = mock_app
+        .execute_contract(
+            sender_addr.clone(),
+            addr_manager.clone(),
+            &register_msg,
+            &[],
+        )
+        .expect("Failed to pass through the first mint message");
+    let register_msg = ExecuteMsg::PassThrough {
+        collection: addr_collection.to_string(),
+        message: CollectionExecuteMsg::Mint {
+            token_id: name_bob.clone(),
+            owner: owner_addr.to_string(),
+            token_uri: None,
+            extension: None,
+        },
+        options: None,
+    };
+
+    // Act
+    let result = mock_app.execute_contract(
+        sender_addr.clone(),
+        addr_manager.clone(),
+        &register_msg,
+        &[],
+    );
+
+    // Assert
+    assert!(
+        result.is_ok(),
+        "Failed to pass through the second mint message"
+    );
+    let result = result.unwrap();
+    let expected_cw721_event = Event::new("wasm")
+        .add_attribute("_contract_address", addr_collection.to_string())
+        .add_attribute("action", "mint")
+        .add_attribute("token_id", name_bob.to_string())
+        .add_attribute("owner", owner_addr.to_string());
+    result.assert_event(&expected_cw721_event);
+    let expected_manager_event =
+        Event::new("wasm-my-collection-manager").add_attribute("token-count-before", "1");
+    result.assert_event(&expected_manager_event);
+    let expected_manager_event =
+        Event::new("wasm-my-collection-manager").add_attribute("token-count-after", "2");
+    result.assert_event(&expected_manager_event);
+    assert_eq!(
+        mock_app
+            .wrap()
+            .query_wasm_smart::<OwnerOfResponse>(
+                addr_collection.to_owned(),
+                &CollectionQueryMsg::OwnerOf {
+                    token_id: name_alice.to_string(),
+                    include_expired: None,
+                }
+            )
+            .expect("Failed to query alice name"),
+        OwnerOfResponse {
+            owner: owner_addr.to_string(),
+            approvals: vec![],
+        }
+    );
+    assert_eq!(
+        mock_app
+            .wrap()
+            .query_wasm_smart::<OwnerOfResponse>(
+                addr_collection,
+                &CollectionQueryMsg::OwnerOf {
+                    token_id: name_bob.to_string(),
+                    include_expired: None,
+                }
+            )
+            .expect("Failed to query bob name"),
+        OwnerOfResponse {
+            owner: owner_addr.to_string(),
+            approvals: vec![],
+        }
+    );
+}
+
+#[test]
+fn test_sudo_update_payment_params() {
+    // Arrange
+    let mut mock_app = App::default();
+    let beneficiary_addr = Addr::unchecked("beneficiary");
+    let (_, addr_manager) = instantiate_collection_manager(
+        &mut mock_app,
+        payment_params(beneficiary_addr.to_owned(), vec![]),
+    );
+    let new_payment_params = payment_params(
+        beneficiary_addr.to_owned(),
+        vec![Coin {
+            denom: "silver".to_owned(),
+            amount: Uint128::from(23u16),
+        }],
+    );
+    let update_sudo_msg = SudoMsg::UpdatePaymentParams(new_payment_params.to_owned());
+    let sudo_msg = cw_multi_test::SudoMsg::Wasm(
+        WasmSudo::new(&addr_manager, &update_sudo_msg).expect("Failed to serialize sudo message"),
+    );
+
+    // Act
+    let result = mock_app.sudo(sudo_msg);
+
+    // Assert
     assert!(result.is_ok(), "Failed to pass through the message");
     let result = result.unwrap();
     let expected_sudo_event = Event::new("wasm-my-collection-manager")
         .add_attribute("_contract_address", addr_manager.to_owned())
         .add_attribute("update-payment-params-beneficiary", beneficiary_addr)
+        .add_attribute("update-payment-params-beneficiary-share-bps", "10000")
         .add_attribute("update-payment-params-mint-price-denom", "silver")
         .add_attribute("update-payment-params-mint-price-amount", "23");
     result.assert_event(&expected_sudo_event);
@@ -405,6 +2293,583 @@ fn test_sudo_update_payment_params() {
     );
 }
 
+#[test]
+fn test_collection_payment_params() {
+    // Arrange
+    let sender_addr = Addr::unchecked("sender");
+    let mut mock_app = AppBuilder::default().build(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(
+                storage,
+                &sender_addr,
+                vec![Coin {
+                    amount: Uint128::from(100u16),
+                    denom: "silver".to_owned(),
+                }],
+            )
+            .expect("Failed to init bank balances");
+    });
+    let beneficiary = Addr::unchecked("beneficiary");
+    let collection_beneficiary = Addr::unchecked("collection-beneficiary");
+    let (_, addr_manager) = instantiate_collection_manager(
+        &mut mock_app,
+        payment_params(beneficiary.to_owned(), vec![]),
+    );
+    let (_, addr_collection) = instantiate_nameservice(&mut mock_app, addr_manager.to_string());
+    register_collection(&mut mock_app, &addr_manager, &addr_collection);
+    let collection_payment_params = payment_params(
+        collection_beneficiary.to_owned(),
+        vec![Coin {
+            amount: Uint128::from(40u16),
+            denom: "silver".to_owned(),
+        }],
+    );
+    let update_sudo_msg = SudoMsg::UpdateCollectionPaymentParams {
+        collection: addr_collection.to_string(),
+        payment_params: collection_payment_params.to_owned(),
+    };
+    let _ = mock_app
+        .sudo(cw_multi_test::SudoMsg::Wasm(
+            WasmSudo::new(&addr_manager, &update_sudo_msg)
+                .expect("Failed to serialize sudo message"),
+        ))
+        .expect("Failed to set collection payment params");
+    let register_msg = ExecuteMsg::PassThrough {
+        collection: addr_collection.to_string(),
+        message: CollectionExecuteMsg::Mint {
+            token_id: "alice".to_owned(),
+            owner: sender_addr.to_string(),
+            token_uri: None,
+            extension: None,
+        },
+        options: None,
+    };
+
+    // Act
+    let result = mock_app.execute_contract(
+        sender_addr.clone(),
+        addr_manager.clone(),
+        &register_msg,
+        &[Coin {
+            amount: Uint128::from(40u16),
+            denom: "silver".to_owned(),
+        }],
+    );
+
+    // Assert
+    assert!(result.is_ok(), "Failed to pass through the message");
+    assert_eq!(
+        vec![Coin {
+            amount: Uint128::from(40u16),
+            denom: "silver".to_owned(),
+        }],
+        mock_app
+            .wrap()
+            .query_all_balances(collection_beneficiary)
+            .expect("Failed to get collection beneficiary balances")
+    );
+    assert_eq!(
+        Vec::<Coin>::new(),
+        mock_app
+            .wrap()
+            .query_all_balances(beneficiary.to_owned())
+            .expect("Failed to get beneficiary balances")
+    );
+    let result = mock_app
+        .wrap()
+        .query_wasm_smart::<ListCollectionPaymentParamsResponse>(
+            &addr_manager,
+            &QueryMsg::ListCollectionPaymentParams {
+                start_after: None,
+                limit: None,
+            },
+        );
+    assert!(result.is_ok(), "Failed to list collection payment params");
+    assert_eq!(
+        result.unwrap(),
+        ListCollectionPaymentParamsResponse {
+            collections: vec![CollectionPaymentParams {
+                collection: addr_collection.to_owned(),
+                payment_params: collection_payment_params,
+            }],
+        }
+    );
+    let clear_sudo_msg = SudoMsg::ClearCollectionPaymentParams {
+        collection: addr_collection.to_string(),
+    };
+    let _ = mock_app
+        .sudo(cw_multi_test::SudoMsg::Wasm(
+            WasmSudo::new(&addr_manager, &clear_sudo_msg)
+                .expect("Failed to serialize sudo message"),
+        ))
+        .expect("Failed to clear collection payment params");
+    let result = mock_app
+        .wrap()
+        .query_wasm_smart::<GetCollectionPaymentParamsResponse>(
+            &addr_manager,
+            &QueryMsg::GetCollectionPaymentParams {
+                collection: addr_collection.to_string(),
+            },
+        );
+    assert!(result.is_ok(), "Failed to query collection payment params");
+    assert_eq!(
+        result.unwrap(),
+        GetCollectionPaymentParamsResponse {
+            collection: addr_collection,
+            payment_params: payment_params(beneficiary, vec![]),
+            is_default: true,
+        }
+    );
+}
+
+#[test]
+fn test_transfer_ownership_and_update_payment_params() {
+    // Arrange
+    let mut mock_app = App::default();
+    let beneficiary_addr = Addr::unchecked("beneficiary");
+    let (_, addr_manager) = instantiate_collection_manager(
+        &mut mock_app,
+        payment_params(beneficiary_addr.to_owned(), vec![]),
+    );
+    let old_owner_addr = Addr::unchecked("deployer-manager");
+    let new_owner_addr = Addr::unchecked("new-owner");
+    let _ = mock_app
+        .execute_contract(
+            old_owner_addr.to_owned(),
+            addr_manager.clone(),
+            &ExecuteMsg::UpdateOwnership(Action::TransferOwnership {
+                new_owner: new_owner_addr.to_string(),
+                expiry: None,
+            }),
+            &[],
+        )
+        .expect("Failed to propose new owner");
+    let _ = mock_app
+        .execute_contract(
+            new_owner_addr.to_owned(),
+            addr_manager.clone(),
+            &ExecuteMsg::UpdateOwnership(Action::AcceptOwnership),
+            &[],
+        )
+        .expect("Failed to accept ownership");
+    let new_payment_params = payment_params(
+        beneficiary_addr.to_owned(),
+        vec![Coin {
+            denom: "silver".to_owned(),
+            amount: Uint128::from(23u16),
+        }],
+    );
+    let update_msg = ExecuteMsg::UpdatePaymentParams(new_payment_params.to_owned());
+    let result = mock_app.execute_contract(old_owner_addr, addr_manager.clone(), &update_msg, &[]);
+    assert!(
+        result.is_err(),
+        "Previous owner should no longer be allowed"
+    );
+
+    // Act
+    let result = mock_app.execute_contract(
+        new_owner_addr.to_owned(),
+        addr_manager.clone(),
+        &update_msg,
+        &[],
+    );
+
+    // Assert
+    assert!(result.is_ok(), "Failed to update payment params");
+    let ownership = mock_app
+        .wrap()
+        .query_wasm_smart::<Ownership<String>>(&addr_manager, &QueryMsg::Ownership {})
+        .expect("Failed to query ownership");
+    assert_eq!(
+        ownership,
+        Ownership {
+            owner: Some(new_owner_addr.to_string()),
+            pending_owner: None,
+            pending_expiry: None,
+        }
+    );
+    let result = mock_app
+        .wrap()
+        .query_wasm_smart::<GetPaymentParamsResponse>(&addr_manager, &QueryMsg::GetPaymentParams);
+    assert!(result.is_ok(), "Failed to query payment params");
+    assert_eq!(
+        result.unwrap(),
+        GetPaymentParamsResponse {
+            payment_params: new_payment_params
+        }
+    );
+}
+
+#[test]
+fn test_payment_manager_role() {
+    // Arrange
+    let mut mock_app = App::default();
+    let beneficiary_addr = Addr::unchecked("beneficiary");
+    let (_, addr_manager) = instantiate_collection_manager(
+        &mut mock_app,
+        payment_params(beneficiary_addr.to_owned(), vec![]),
+    );
+    let owner_addr = Addr::unchecked("deployer-manager");
+    let payment_manager_addr = Addr::unchecked("payment-manager");
+    let new_payment_params = payment_params(
+        beneficiary_addr,
+        vec![Coin {
+            denom: "silver".to_owned(),
+            amount: Uint128::from(23u16),
+        }],
+    );
+    let update_msg = ExecuteMsg::UpdatePaymentParams(new_payment_params.to_owned());
+    let result = mock_app.execute_contract(
+        payment_manager_addr.to_owned(),
+        addr_manager.clone(),
+        &update_msg,
+        &[],
+    );
+    assert!(result.is_err(), "Should not update without the role");
+    let result = mock_app
+        .execute_contract(
+            owner_addr,
+            addr_manager.clone(),
+            &ExecuteMsg::GrantRole {
+                role: Role::PaymentManager,
+                member: payment_manager_addr.to_string(),
+            },
+            &[],
+        )
+        .expect("Failed to grant role");
+    result.assert_event(
+        &Event::new("wasm-my-collection-manager")
+            .add_attribute("grant-role", "payment-manager")
+            .add_attribute("grant-role-member", payment_manager_addr.to_string()),
+    );
+
+    // Act
+    let result = mock_app.execute_contract(
+        payment_manager_addr.to_owned(),
+        addr_manager.clone(),
+        &update_msg,
+        &[],
+    );
+
+    // Assert
+    assert!(result.is_ok(), "Failed to update payment params");
+    let members = mock_app
+        .wrap()
+        .query_wasm_smart::<ListRoleMembersResponse>(
+            &addr_manager,
+            &QueryMsg::ListRoleMembers {
+                role: Role::PaymentManager,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .expect("Failed to list role members");
+    assert_eq!(
+        members,
+        ListRoleMembersResponse {
+            members: vec![payment_manager_addr.to_owned()],
+        }
+    );
+    let _ = mock_app
+        .execute_contract(
+            payment_manager_addr.to_owned(),
+            addr_manager.clone(),
+            &ExecuteMsg::RenounceRole {
+                role: Role::PaymentManager,
+            },
+            &[],
+        )
+        .expect("Failed to renounce role");
+    let has_role = mock_app
+        .wrap()
+        .query_wasm_smart::<HasRoleResponse>(
+            &addr_manager,
+            &QueryMsg::HasRole {
+                role: Role::PaymentManager,
+                address: payment_manager_addr.to_string(),
+            },
+        )
+        .expect("Failed to query role");
+    assert_eq!(has_role, HasRoleResponse { has_role: false });
+    let result = mock_app
+        .wrap()
+        .query_wasm_smart::<GetPaymentParamsResponse>(&addr_manager, &QueryMsg::GetPaymentParams);
+    assert!(result.is_ok(), "Failed to query payment params");
+    assert_eq!(
+        result.unwrap(),
+        GetPaymentParamsResponse {
+            payment_params: new_payment_params
+        }
+    );
+}
+
+#[test]
+fn test_pass_through_policies() {
+    // Arrange
+    let mut mock_app = App::default();
+    let (_, addr_manager) = instantiate_collection_manager(
+        &mut mock_app,
+        payment_params(Addr::unchecked("beneficiary"), vec![]),
+    );
+    let (_, addr_collection) = instantiate_nameservice(&mut mock_app, addr_manager.to_string());
+    register_collection(&mut mock_app, &addr_manager, &addr_collection);
+    let owner_addr = Addr::unchecked("deployer-manager");
+    let minter_addr = Addr::unchecked("minter");
+    let policies = mock_app
+        .wrap()
+        .query_wasm_smart::<GetPassThroughPoliciesResponse>(
+            &addr_manager,
+            &QueryMsg::GetPassThroughPolicies,
+        )
+        .expect("Failed to query policies")
+        .policies;
+    assert!(policies.contains(&KindPassThroughPolicy {
+        kind: CollectionMsgKind::Mint,
+        policy: PassThroughPolicy::Anyone,
+    }));
+    assert!(policies.contains(&KindPassThroughPolicy {
+        kind: CollectionMsgKind::UpdateMinterOwnership,
+        policy: PassThroughPolicy::Forbidden,
+    }));
+    for execute_msg in [
+        ExecuteMsg::SetPassThroughPolicy {
+            kind: CollectionMsgKind::Mint,
+            policy: PassThroughPolicy::Role(Role::Minter),
+        },
+        ExecuteMsg::GrantRole {
+            role: Role::Minter,
+            member: minter_addr.to_string(),
+        },
+    ] {
+        let _ = mock_app
+            .execute_contract(
+                owner_addr.to_owned(),
+                addr_manager.clone(),
+                &execute_msg,
+                &[],
+            )
+            .expect("Failed to configure minting");
+    }
+    let register_msg = ExecuteMsg::PassThrough {
+        collection: addr_collection.to_string(),
+        message: CollectionExecuteMsg::Mint {
+            token_id: "alice".to_owned(),
+            owner: "owner".to_owned(),
+            token_uri: None,
+            extension: None,
+        },
+        options: None,
+    };
+    let result = mock_app.execute_contract(
+        Addr::unchecked("sender"),
+        addr_manager.clone(),
+        &register_msg,
+        &[],
+    );
+    assert!(result.is_err(), "Should not mint without the minter role");
+
+    // Act
+    let result = mock_app.execute_contract(minter_addr, addr_manager.clone(), &register_msg, &[]);
+
+    // Assert
+    assert!(result.is_ok(), "Failed to mint as minter");
+    let burn_msg = ExecuteMsg::PassThrough {
+        collection: addr_collection.to_string(),
+        message: CollectionExecuteMsg::Burn {
+            token_id: "alice".to_owned(),
+        },
+        options: None,
+    };
+    let result = mock_app.execute_contract(owner_addr, addr_manager, &burn_msg, &[]);
+    assert!(result.is_err(), "Burn should be forbidden by default");
+}
+
+#[test]
+fn test_pauser_role_pauses_minting() {
+    // Arrange
+    let mut mock_app = App::default();
+    let (_, addr_manager) = instantiate_collection_manager(
+        &mut mock_app,
+        payment_params(Addr::unchecked("beneficiary"), vec![]),
+    );
+    let (_, addr_collection) = instantiate_nameservice(&mut mock_app, addr_manager.to_string());
+    register_collection(&mut mock_app, &addr_manager, &addr_collection);
+    let pauser_addr = Addr::unchecked("pauser");
+    let _ = mock_app
+        .execute_contract(
+            Addr::unchecked("deployer-manager"),
+            addr_manager.clone(),
+            &ExecuteMsg::GrantRole {
+                role: Role::Pauser,
+                member: pauser_addr.to_string(),
+            },
+            &[],
+        )
+        .expect("Failed to grant pauser role");
+    let pause_msg = ExecuteMsg::Pause {
+        collection: None,
+        until_height: None,
+    };
+    let result = mock_app.execute_contract(
+        Addr::unchecked("sender"),
+        addr_manager.clone(),
+        &pause_msg,
+        &[],
+    );
+    assert!(result.is_err(), "Should not pause without the pauser role");
+
+    // Act
+    let result = mock_app
+        .execute_contract(
+            pauser_addr.to_owned(),
+            addr_manager.clone(),
+            &pause_msg,
+            &[],
+        )
+        .expect("Failed to pause");
+
+    // Assert
+    let expected_event = Event::new("wasm-my-collection-manager")
+        .add_attribute("pause", "global")
+        .add_attribute("pause-until-height", "none");
+    result.assert_event(&expected_event);
+    let status = mock_app
+        .wrap()
+        .query_wasm_smart::<PauseStatusResponse>(
+            &addr_manager,
+            &QueryMsg::PauseStatus {
+                collection: Some(addr_collection.to_string()),
+            },
+        )
+        .expect("Failed to query pause status");
+    assert_eq!(
+        status,
+        PauseStatusResponse {
+            paused: true,
+            global: Some(PauseInfo { until_height: None }),
+            collection: None,
+        }
+    );
+    let mint_msg = ExecuteMsg::PassThrough {
+        collection: addr_collection.to_string(),
+        message: CollectionExecuteMsg::Mint {
+            token_id: "alice".to_owned(),
+            owner: "owner".to_owned(),
+            token_uri: None,
+            extension: None,
+        },
+        options: None,
+    };
+    let result = mock_app.execute_contract(
+        Addr::unchecked("sender"),
+        addr_manager.clone(),
+        &mint_msg,
+        &[],
+    );
+    assert!(result.is_err(), "Should not mint while paused");
+    let _ = mock_app
+        .execute_contract(
+            pauser_addr,
+            addr_manager.clone(),
+            &ExecuteMsg::Unpause { collection: None },
+            &[],
+        )
+        .expect("Failed to unpause");
+    let result = mock_app.execute_contract(Addr::unchecked("sender"), addr_manager, &mint_msg, &[]);
+    assert!(result.is_ok(), "Failed to mint after unpausing");
+}
+
+#[test]
+fn test_registrar_mints_reserved_name() {
+    // Arrange
+    let mut mock_app = App::default();
+    let (_, addr_manager) = instantiate_collection_manager(
+        &mut mock_app,
+        payment_params(Addr::unchecked("beneficiary"), vec![]),
+    );
+    let (_, addr_collection) = instantiate_nameservice(&mut mock_app, addr_manager.to_string());
+    register_collection(&mut mock_app, &addr_manager, &addr_collection);
+    let registrar_addr = Addr::unchecked("registrar");
+    let rules = NameRules {
+        charset: Charset::AsciiLowercase,
+        min_length: 1,
+        max_length: 20,
+        reserved_names: vec!["root".to_owned()],
+    };
+    let set_rules_msg = ExecuteMsg::SetNameRules {
+        collection: addr_collection.to_string(),
+        rules: rules.to_owned(),
+    };
+    let result = mock_app.execute_contract(
+        registrar_addr.to_owned(),
+        addr_manager.clone(),
+        &set_rules_msg,
+        &[],
+    );
+    assert!(result.is_err(), "Only the owner should set name rules");
+    let _ = mock_app
+        .execute_contract(
+            Addr::unchecked("deployer-manager"),
+            addr_manager.clone(),
+            &set_rules_msg,
+            &[],
+        )
+        .expect("Failed to set name rules");
+    let _ = mock_app
+        .execute_contract(
+            Addr::unchecked("deployer-manager"),
+            addr_manager.clone(),
+            &ExecuteMsg::GrantRole {
+                role: Role::Registrar,
+                member: registrar_addr.to_string(),
+            },
+            &[],
+        )
+        .expect("Failed to grant registrar role");
+    let mint_root_msg = ExecuteMsg::PassThrough {
+        collection: addr_collection.to_string(),
+        message: CollectionExecuteMsg::Mint {
+            token_id: "root".to_owned(),
+            owner: "owner".to_owned(),
+            token_uri: None,
+            extension: None,
+        },
+        options: None,
+    };
+    let result = mock_app.execute_contract(
+        Addr::unchecked("sender"),
+        addr_manager.clone(),
+        &mint_root_msg,
+        &[],
+    );
+    assert!(result.is_err(), "Should not mint a reserved name");
+
+    // Act
+    let result = mock_app.execute_contract(
+        registrar_addr.to_owned(),
+        addr_manager.clone(),
+        &mint_root_msg,
+        &[],
+    );
+
+    // Assert
+    assert!(
+        result.is_ok(),
+        "Failed to mint a reserved name as registrar"
+    );
+    let saved_rules = mock_app
+        .wrap()
+        .query_wasm_smart::<GetNameRulesResponse>(
+            &addr_manager,
+            &QueryMsg::GetNameRules {
+                collection: addr_collection.to_string(),
+            },
+        )
+        .expect("Failed to query name rules");
+    assert_eq!(saved_rules.rules, Some(rules));
+}
+
 #[test]
 fn test_migrate_payment_params() {
     // Arrange old smart contract
@@ -443,20 +2908,21 @@ fn test_migrate_payment_params() {
     );
     let manager_new_code_id = mock_app.store_code(new_code);
     let beneficiary_addr = Addr::unchecked("beneficiary");
-    let new_payment_params = PaymentParams {
-        beneficiary: beneficiary_addr.to_owned(),
-        mint_price: Some(Coin {
+    let new_payment_params = payment_params(
+        beneficiary_addr.to_owned(),
+        vec![Coin {
             denom: "silver".to_owned(),
             amount: Uint128::from(23u16),
-        }),
-    };
+        }],
+    );
     let migrate_msg = MigrateMsg {
         payment_params: new_payment_params.to_owned(),
+        owner: Some(admin_addr.to_string()),
     };
 
     // Act
     let result = mock_app.migrate_contract(
-        admin_addr,
+        admin_addr.to_owned(),
         addr_manager.to_owned(),
         &migrate_msg,
         manager_new_code_id,
@@ -473,8 +2939,10 @@ fn test_migrate_payment_params() {
         .add_attribute("_contract_address", addr_manager.to_owned())
         .add_attribute("update-contract-version", "0.1.0")
         .add_attribute("update-payment-params-beneficiary", beneficiary_addr)
+        .add_attribute("update-payment-params-beneficiary-share-bps", "10000")
         .add_attribute("update-payment-params-mint-price-denom", "silver")
-        .add_attribute("update-payment-params-mint-price-amount", "23");
+        .add_attribute("update-payment-params-mint-price-amount", "23")
+        .add_attribute("update-owner", admin_addr);
     result.assert_event(&expected_migrate_event2);
     let result = mock_app
         .wrap()